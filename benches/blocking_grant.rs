@@ -0,0 +1,81 @@
+//! Compares the latency of blocking grant strategies.
+//!
+//! Two threads pass a single element back and forth through a pair of circular buffers, so each
+//! round trip includes two waits for a grant.
+//!
+//! Run with `cargo bench --bench blocking_grant`.
+
+use rivulet::{circular_buffer, view::BlockingStrategy, SplittableView, View, ViewMut};
+use std::time::{Duration, Instant};
+
+const ROUND_TRIPS: usize = 10000;
+
+fn grant(view: &mut impl View, strategy: Option<BlockingStrategy>) {
+    match strategy {
+        Some(strategy) => view.blocking_grant_with(1, strategy).unwrap(),
+        None => view.blocking_grant(1).unwrap(),
+    }
+}
+
+fn round_trips(strategy: Option<BlockingStrategy>) -> Duration {
+    let (mut ping_sink, ping_source) = circular_buffer::<u64>(1);
+    let (mut pong_sink, pong_source) = circular_buffer::<u64>(1);
+    let mut ping_source = ping_source.into_view();
+    let mut pong_source = pong_source.into_view();
+
+    let echo = std::thread::spawn(move || {
+        for _ in 0..ROUND_TRIPS {
+            grant(&mut ping_source, strategy);
+            grant(&mut pong_sink, strategy);
+            pong_sink.view_mut()[0] = ping_source.view()[0];
+            ping_source.release(1);
+            pong_sink.release(1);
+        }
+    });
+
+    let start = Instant::now();
+    for i in 0..ROUND_TRIPS {
+        grant(&mut ping_sink, strategy);
+        ping_sink.view_mut()[0] = i as u64;
+        ping_sink.release(1);
+        grant(&mut pong_source, strategy);
+        assert_eq!(pong_source.view()[0], i as u64);
+        pong_source.release(1);
+    }
+    let elapsed = start.elapsed();
+
+    echo.join().unwrap();
+    elapsed
+}
+
+fn main() {
+    let strategies = [
+        ("block_on", None),
+        ("spin", Some(BlockingStrategy::Spin)),
+        (
+            "spin then yield",
+            Some(BlockingStrategy::SpinThenYield(100)),
+        ),
+        ("spin then park", Some(BlockingStrategy::SpinThenPark(100))),
+        ("park", Some(BlockingStrategy::SpinThenPark(0))),
+    ];
+
+    // Spinning without yielding can't make progress until the thread is preempted.
+    let parallel = std::thread::available_parallelism().map_or(1, |n| n.get()) > 1;
+
+    println!("{:<16} {:>14}", "strategy", "round trip");
+    for (name, strategy) in strategies {
+        if !parallel && strategy == Some(BlockingStrategy::Spin) {
+            println!("{:<16} {:>14}", name, "skipped");
+            continue;
+        }
+        // Warm up, then take the best of several runs to reduce scheduling noise.
+        round_trips(strategy);
+        let best = (0..5).map(|_| round_trips(strategy)).min().unwrap();
+        println!(
+            "{:<16} {:>11.0} ns",
+            name,
+            best.as_nanos() as f64 / ROUND_TRIPS as f64
+        );
+    }
+}
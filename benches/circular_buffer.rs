@@ -0,0 +1,162 @@
+//! Measures circular buffer throughput and wake latency.
+//!
+//! Run with `cargo bench --bench circular_buffer`.
+
+use rivulet::{circular_buffer, SplittableView, View, ViewMut};
+use std::{
+    mem::size_of,
+    time::{Duration, Instant},
+};
+
+/// The number of bytes transferred by each throughput benchmark.
+const BYTES: usize = 64 << 20;
+
+/// The number of samples taken by the latency benchmark.
+const WAKES: usize = 10000;
+
+/// The readers attached to the buffer.
+#[derive(Copy, Clone)]
+enum Readers {
+    /// A single view.
+    View,
+    /// A number of cloneable views.
+    Cloneable(usize),
+    /// A view that reads after another view, produced by `sequence`.
+    Sequence,
+}
+
+fn write<T: Default + Copy>(mut sink: impl ViewMut<Item = T>, grant: usize, count: usize) {
+    let mut written = 0;
+    while written < count {
+        sink.blocking_grant(grant).unwrap();
+        let len = sink.view_mut().len().min(grant).min(count - written);
+        sink.view_mut()[..len].fill(T::default());
+        sink.release(len);
+        written += len;
+    }
+}
+
+fn read<T>(mut source: impl View<Item = T>, grant: usize) -> usize {
+    let mut count = 0;
+    loop {
+        source.blocking_grant(grant).unwrap();
+        let len = source.view().len();
+        if len == 0 {
+            break count;
+        }
+        count += len;
+        source.release(len);
+    }
+}
+
+/// Transfer `BYTES` bytes of `T`, `grant` elements at a time, returning the elapsed time.
+fn throughput<T>(grant: usize, readers: Readers) -> Duration
+where
+    T: Default + Copy + Send + Sync + 'static,
+{
+    let count = BYTES / size_of::<T>();
+    let (sink, source) = circular_buffer::<T>(grant * 16);
+
+    let start = Instant::now();
+    let readers = match readers {
+        Readers::View => vec![std::thread::spawn(move || read(source.into_view(), grant))],
+        Readers::Cloneable(n) => {
+            let source = source.into_cloneable_view();
+            (0..n)
+                .map(|_| {
+                    let source = source.clone();
+                    std::thread::spawn(move || read(source, grant))
+                })
+                .collect()
+        }
+        Readers::Sequence => {
+            let (first, second) = source.sequence();
+            vec![
+                std::thread::spawn(move || read(first.into_view(), grant)),
+                std::thread::spawn(move || read(second.into_view(), grant)),
+            ]
+        }
+    };
+    write(sink, grant, count);
+    for reader in readers {
+        assert_eq!(reader.join().unwrap(), count);
+    }
+    start.elapsed()
+}
+
+/// Measure the time from releasing an element to the reader obtaining it.
+fn wake_latency() -> Duration {
+    let (mut sink, source) = circular_buffer::<u64>(1);
+    let (mut ack_sink, ack_source) = circular_buffer::<u64>(1);
+    let mut ack_source = ack_source.into_view();
+
+    let start = Instant::now();
+    let reader = std::thread::spawn(move || {
+        let mut source = source.into_view();
+        let mut total = Duration::ZERO;
+        for _ in 0..WAKES {
+            source.blocking_grant(1).unwrap();
+            let sent = Duration::from_nanos(source.view()[0]);
+            total += start.elapsed() - sent;
+            source.release(1);
+            ack_sink.blocking_grant(1).unwrap();
+            ack_sink.release(1);
+        }
+        total
+    });
+
+    for _ in 0..WAKES {
+        sink.blocking_grant(1).unwrap();
+        sink.view_mut()[0] = start.elapsed().as_nanos() as u64;
+        sink.release(1);
+        ack_source.blocking_grant(1).unwrap();
+        ack_source.release(1);
+    }
+    reader.join().unwrap() / WAKES as u32
+}
+
+/// Run a benchmark several times, returning the best time.
+fn best(f: impl Fn() -> Duration) -> Duration {
+    f();
+    (0..3).map(|_| f()).min().unwrap()
+}
+
+fn report<T>(grant: usize, readers: Readers)
+where
+    T: Default + Copy + Send + Sync + 'static,
+{
+    let readers_name = match readers {
+        Readers::View => "view".to_string(),
+        Readers::Cloneable(n) => format!("cloneable x{}", n),
+        Readers::Sequence => "sequence".to_string(),
+    };
+    let elapsed = best(|| throughput::<T>(grant, readers)).as_secs_f64();
+    println!(
+        "{:<10} {:>6} {:<14} {:>10.1} MB/s {:>10.1} Melem/s",
+        std::any::type_name::<T>(),
+        grant,
+        readers_name,
+        BYTES as f64 / elapsed / 1e6,
+        (BYTES / size_of::<T>()) as f64 / elapsed / 1e6,
+    );
+}
+
+fn main() {
+    println!("throughput");
+    for grant in [64, 1024] {
+        report::<u8>(grant, Readers::View);
+        report::<u64>(grant, Readers::View);
+        report::<[u64; 8]>(grant, Readers::View);
+    }
+    for readers in [
+        Readers::Cloneable(1),
+        Readers::Cloneable(2),
+        Readers::Cloneable(4),
+        Readers::Sequence,
+    ] {
+        report::<u64>(1024, readers);
+    }
+
+    println!();
+    println!("wake latency: {} ns", best(wake_latency).as_nanos());
+}
@@ -0,0 +1,233 @@
+//! Reinterpreting streams of bytes as other types.
+//!
+//! Byte streams often contain samples of other types, such as `i16` or `f32`.
+//! [`Cast`] reinterprets a stream of bytes as a stream of any [`Pod`] type without copying, and
+//! [`Bytes`] does the reverse.
+//!
+//! Reinterpreted views are only valid if each view of the underlying stream is aligned to the
+//! new type.
+//! Streams that are only ever advanced by whole elements, such as a
+//! [`circular_buffer`](`crate::circular_buffer()`) of bytes, always satisfy this.
+
+use crate::{View, ViewMut};
+use core::{
+    marker::PhantomData,
+    mem::{align_of, size_of},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Plain-old-data types, which can be safely reinterpreted from any bytes.
+///
+/// # Safety
+/// The type must not contain any padding, and every bit pattern must be a valid value.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    { $($type:ty),* } => {
+        $(unsafe impl Pod for $type {})*
+    }
+}
+
+impl_pod! { u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64 }
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+fn check_alignment<T>(ptr: *const u8) {
+    assert_eq!(
+        ptr as usize % align_of::<T>(),
+        0,
+        "view is not aligned to the reinterpreted type"
+    );
+}
+
+fn elements_containing<T>(bytes: usize) -> usize {
+    let elements = bytes / size_of::<T>();
+    if elements * size_of::<T>() < bytes {
+        elements + 1
+    } else {
+        elements
+    }
+}
+
+/// Reinterprets a view of bytes as a view of `T`.
+///
+/// Grants and releases are made in whole elements.
+/// If the stream ends with a partial element, the partial element is never viewed.
+///
+/// # Panics
+/// Viewing the stream panics if the underlying view isn't aligned to `T`.
+#[derive(Copy, Clone, Debug)]
+pub struct Cast<V, T> {
+    view: V,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<V, T> Cast<V, T>
+where
+    V: View<Item = u8>,
+    T: Pod,
+{
+    /// Create a new reinterpreted view.
+    ///
+    /// # Panics
+    /// Panics if `T` is zero-sized.
+    pub fn new(view: V) -> Self {
+        assert!(size_of::<T>() > 0, "zero-sized types are not supported");
+        Self {
+            view,
+            _type: PhantomData,
+        }
+    }
+
+    /// Return the original view.
+    pub fn into_inner(self) -> V {
+        self.view
+    }
+}
+
+impl<V, T> View for Cast<V, T>
+where
+    V: View<Item = u8>,
+    T: Pod,
+{
+    type Item = T;
+    type Error = V::Error;
+
+    fn view(&self) -> &[T] {
+        let bytes = self.view.view();
+        if bytes.len() < size_of::<T>() {
+            return &[];
+        }
+        check_alignment::<T>(bytes.as_ptr());
+        // Safety: the pointer is aligned and `T` is valid for any bytes
+        unsafe {
+            core::slice::from_raw_parts(bytes.as_ptr() as *const T, bytes.len() / size_of::<T>())
+        }
+    }
+
+    fn poll_grant(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        count: usize,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.view).poll_grant(cx, count.saturating_mul(size_of::<T>()))
+    }
+
+    fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
+        self.view.try_grant(count.saturating_mul(size_of::<T>()))
+    }
+
+    fn release(&mut self, count: usize) {
+        self.view.release(count * size_of::<T>())
+    }
+}
+
+impl<V, T> ViewMut for Cast<V, T>
+where
+    V: ViewMut<Item = u8>,
+    T: Pod,
+{
+    fn view_mut(&mut self) -> &mut [T] {
+        let bytes = self.view.view_mut();
+        if bytes.len() < size_of::<T>() {
+            return &mut [];
+        }
+        check_alignment::<T>(bytes.as_ptr());
+        // Safety: the pointer is aligned and any bytes written are valid
+        unsafe {
+            core::slice::from_raw_parts_mut(
+                bytes.as_mut_ptr() as *mut T,
+                bytes.len() / size_of::<T>(),
+            )
+        }
+    }
+}
+
+/// Reinterprets a view of `T` as a view of bytes.
+///
+/// # Panics
+/// Releasing the stream panics if the release doesn't contain whole elements.
+#[derive(Copy, Clone, Debug)]
+pub struct Bytes<V> {
+    view: V,
+}
+
+impl<V> Bytes<V>
+where
+    V: View,
+    V::Item: Pod,
+{
+    /// Create a new reinterpreted view.
+    ///
+    /// # Panics
+    /// Panics if the item type is zero-sized.
+    pub fn new(view: V) -> Self {
+        assert!(
+            size_of::<V::Item>() > 0,
+            "zero-sized types are not supported"
+        );
+        Self { view }
+    }
+
+    /// Return the original view.
+    pub fn into_inner(self) -> V {
+        self.view
+    }
+}
+
+impl<V> View for Bytes<V>
+where
+    V: View,
+    V::Item: Pod,
+{
+    type Item = u8;
+    type Error = V::Error;
+
+    fn view(&self) -> &[u8] {
+        let view = self.view.view();
+        // Safety: any `Pod` type can be viewed as bytes
+        unsafe {
+            core::slice::from_raw_parts(view.as_ptr() as *const u8, core::mem::size_of_val(view))
+        }
+    }
+
+    fn poll_grant(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        count: usize,
+    ) -> Poll<Result<(), Self::Error>> {
+        let count = elements_containing::<V::Item>(count);
+        Pin::new(&mut self.view).poll_grant(cx, count)
+    }
+
+    fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
+        self.view.try_grant(elements_containing::<V::Item>(count))
+    }
+
+    fn release(&mut self, count: usize) {
+        assert_eq!(
+            count % size_of::<V::Item>(),
+            0,
+            "attempted to release a partial element"
+        );
+        self.view.release(count / size_of::<V::Item>())
+    }
+}
+
+impl<V> ViewMut for Bytes<V>
+where
+    V: ViewMut,
+    V::Item: Pod,
+{
+    fn view_mut(&mut self) -> &mut [u8] {
+        let view = self.view.view_mut();
+        // Safety: any bytes written are valid for `Pod` types
+        unsafe {
+            core::slice::from_raw_parts_mut(
+                view.as_mut_ptr() as *mut u8,
+                core::mem::size_of_val(view),
+            )
+        }
+    }
+}
@@ -1,24 +1,54 @@
-#![cfg(all(feature = "std"))]
+#![cfg(feature = "std")]
 #![cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
 //! An asynchronous copy-free circular buffer.
 //!
 //! This buffer is optimized for contiguous memory segments and never copies data to other regions
 //! of the buffer.
+//!
+//! # Spilling
+//! A buffer created with [`spilling_circular_buffer`] never blocks the writer.
+//! When the buffer is full, released data is spilled to a temporary file.
+//! Spilled data is moved back into the buffer, in order, as the readers free space, by a
+//! dedicated thread so readers never block on file I/O.
+//!
+//! # Overwriting
+//! A buffer created with [`lossy_circular_buffer`] drops the oldest data instead of blocking the
+//! writer.
+//! When the buffer is full, the writer advances past the oldest data, even if it's granted to the
+//! reader, since the reader's grants are copied out of the buffer.
+//! The [`LossySource`] detects this on its next grant, which produces [`LossyError::Overrun`] with
+//! the number of elements lost, and continues from the oldest retained data.
+//!
+//! # Real-time use
+//! Once created, writing to a [`circular_buffer`] never locks or allocates:
+//! [`Sink::try_grant`](`View::try_grant`), [`Sink::view_mut`](`ViewMut::view_mut`), and
+//! [`Sink::release`](`View::release`) only access atomics and wake the readers.
+//! This makes the sink suitable for real-time threads, such as audio callbacks, as long as:
+//! * the readers are created with
+//!   [`into_view`](`crate::SplittableView::into_view`) or
+//!   [`into_cloneable_view_with_max_readers`](`crate::SplittableView::into_cloneable_view_with_max_readers`),
+//!   since other cloneable views lock when woken, and
+//! * the buffer isn't a [`spilling_circular_buffer`], which writes to a file.
+//!
+//! The sink of a [`lossy_circular_buffer`] also never locks or allocates.
+//!
+//! Waking the readers calls their wakers, which are provided by the executor the readers run on.
 use crate::{
     error::GrantOverflow,
     splittable::{SplittableViewImpl, SplittableViewImplMut},
+    sync::{
+        atomic::{fence, AtomicBool, AtomicU64, Ordering},
+        Arc, AtomicWaker, Mutex,
+    },
     View, ViewMut,
 };
-use futures::task::AtomicWaker;
 use num_integer::{div_ceil, lcm};
+use once_cell::sync::OnceCell;
 use std::{
     convert::TryInto,
+    io::{Read, Seek, SeekFrom, Write},
     mem::{size_of, MaybeUninit},
     pin::Pin,
-    sync::{
-        atomic::{AtomicBool, AtomicU64, Ordering},
-        Arc, Mutex,
-    },
     task::{Context, Poll, Waker},
 };
 
@@ -74,6 +104,13 @@ impl<T> UnsafeCircularBuffer<T> {
         self.size
     }
 
+    // Pointer to the element at `index`, for reading data that may be written concurrently
+    pub unsafe fn ptr(&self, index: u64) -> *const T {
+        let buf_len: u64 = self.len().try_into().unwrap();
+        let offset = index % buf_len;
+        self.ptr.add(offset.try_into().unwrap())
+    }
+
     // Only safe if you can guarantee no mutable references to this range
     pub unsafe fn range(&self, index: u64, len: usize) -> &[T] {
         debug_assert!(len <= self.len());
@@ -92,18 +129,86 @@ impl<T> UnsafeCircularBuffer<T> {
     }
 }
 
+/// Data spilled from a full buffer, stored as raw elements.
+struct SpillFile {
+    file: std::fs::File,
+    read: u64,  // byte offset of the oldest spilled data
+    write: u64, // byte offset of the end of spilled data
+}
+
+impl SpillFile {
+    fn new() -> std::io::Result<Self> {
+        static COUNT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "rivulet-spill-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+
+        // The file is only accessed through the handle, so remove it immediately where possible.
+        let _ = std::fs::remove_file(&path);
+
+        Ok(Self {
+            file,
+            read: 0,
+            write: 0,
+        })
+    }
+
+    fn len(&self) -> usize {
+        (self.write - self.read).try_into().unwrap()
+    }
+
+    fn push(&mut self, data: &[u8]) -> std::io::Result<()> {
+        self.file.seek(SeekFrom::Start(self.write))?;
+        self.file.write_all(data)?;
+        self.write += u64::try_from(data.len()).unwrap();
+        Ok(())
+    }
+
+    fn pop(&mut self, data: &mut [u8]) -> std::io::Result<()> {
+        self.file.seek(SeekFrom::Start(self.read))?;
+        self.file.read_exact(data)?;
+        self.read += u64::try_from(data.len()).unwrap();
+        if self.read == self.write {
+            // Reclaim the space when empty
+            self.read = 0;
+            self.write = 0;
+            self.file.set_len(0)?;
+        }
+        Ok(())
+    }
+}
+
+/// Shared spill state
+struct Spill {
+    spilled: AtomicBool,   // true if the spill file contains data
+    abandoned: AtomicBool, // true if the readers are dropped
+    failed: AtomicBool,    // true if the writer failed to write the spill file
+    file: Mutex<SpillFile>,
+    refiller: OnceCell<std::thread::Thread>, // moves spilled data back into the buffer
+}
+
 /// Shared state
 struct State<T> {
     buffer: UnsafeCircularBuffer<T>,
     closed: AtomicBool,       // true if the stream is closed
-    head: AtomicU64,          // start index of written data
-    tail: AtomicU64,          // start index of unwritten data
+    head: AtomicU64,          // start index of written data, released by the readers
+    tail: AtomicU64,          // start index of unwritten data, released by the writer
     write_waker: AtomicWaker, // waker waited on by the writer
-    read_waker: Mutex<Option<Box<dyn Fn() + Send + Sync>>>, // wake readers when new data is available
+    write_wake_at: AtomicU64, // head required by the waiting writer
+    read_waker: OnceCell<Box<dyn Fn(Option<u64>) + Send + Sync>>, // wake readers with the new tail
+    spill: Option<Spill>,     // only present if the buffer spills when full
+    lossy: bool,              // true if the writer overwrites the oldest data when full
 }
 
 impl<T: Default> State<T> {
-    fn new(minimum_size: usize) -> Self {
+    fn new(minimum_size: usize, spill: Option<SpillFile>, lossy: bool) -> Self {
         // The +1 ensures there's room for a marker element (to indicate the difference between
         // empty and full
         Self {
@@ -112,62 +217,327 @@ impl<T: Default> State<T> {
             head: AtomicU64::new(0),
             tail: AtomicU64::new(0),
             write_waker: AtomicWaker::new(),
-            read_waker: Mutex::new(None),
+            write_wake_at: AtomicU64::new(0),
+            read_waker: OnceCell::new(),
+            spill: spill.map(|file| Spill {
+                spilled: AtomicBool::new(false),
+                abandoned: AtomicBool::new(false),
+                failed: AtomicBool::new(false),
+                file: Mutex::new(file),
+                refiller: OnceCell::new(),
+            }),
+            lossy,
         }
     }
 }
 
 impl<T> State<T> {
+    // Acquires the data written before the tail.
     fn readable_len(&self, start: u64) -> usize {
-        (self.tail.load(Ordering::Relaxed) - start)
+        (self.tail.load(Ordering::Acquire) - start)
             .try_into()
             .unwrap()
     }
 
+    // Acquires the readers' accesses to data before the head, so it can be overwritten.
     fn writeable_len(&self) -> usize {
-        self.buffer.len() - self.readable_len(self.head.load(Ordering::Relaxed))
+        self.buffer.len() - self.readable_len(self.head.load(Ordering::Acquire))
+    }
+
+    fn set_read_waker(&self, waker: impl Fn(Option<u64>) + Send + Sync + 'static) {
+        assert!(
+            self.read_waker.set(Box::new(waker)).is_ok(),
+            "reader waker already set!"
+        );
+        // See `wake_readers`
+        fence(Ordering::SeqCst);
+    }
+
+    // Wake the writer if the head has reached the position it's waiting for.
+    fn wake_writer(&self, head: u64) {
+        // See `Sink::poll_grant`
+        fence(Ordering::SeqCst);
+        if head >= self.write_wake_at.load(Ordering::Relaxed) {
+            self.write_waker.wake();
+        }
+    }
+
+    // Wake the readers, passing the tail, or `None` if the stream is closed.
+    fn wake_readers(&self, tail: Option<u64>) {
+        if let Some(read_waker) = self.read_waker.get() {
+            read_waker(tail)
+        } else {
+            // The reader waker may be set concurrently, after the reader observed a stale tail.
+            // Together with the fence in `set_reader_waker`, either the reader observes the tail
+            // or this observes the reader waker.
+            fence(Ordering::SeqCst);
+            if let Some(read_waker) = self.read_waker.get() {
+                read_waker(tail)
+            }
+        }
+    }
+
+    fn is_spilled(&self) -> bool {
+        match self.spill.as_ref() {
+            Some(spill) => spill.spilled.load(Ordering::Acquire),
+            None => false,
+        }
+    }
+
+    // True if no more data will be made available.
+    // Closing the stream releases the final tail, which is acquired here.
+    fn is_finished(&self) -> bool {
+        self.closed.load(Ordering::Acquire) && !self.is_spilled()
+    }
+
+    // Move as much spilled data as possible back into the buffer.
+    fn refill(&self) -> std::io::Result<()> {
+        if !self.is_spilled() {
+            return Ok(());
+        }
+
+        let spill = self.spill.as_ref().unwrap();
+        let mut file = spill.file.lock().expect("another thread panicked");
+        let len = self.writeable_len().min(file.len() / size_of::<T>());
+        if len == 0 {
+            return Ok(());
+        }
+
+        // Safety: the writer doesn't write to the buffer while data is spilled, and spilled data
+        // always consists of valid `Copy` elements.
+        let tail = self.tail.load(Ordering::Relaxed);
+        let end = tail + u64::try_from(len).unwrap();
+        unsafe {
+            let range = self.buffer.range_mut(tail, len);
+            let bytes =
+                std::slice::from_raw_parts_mut(range.as_mut_ptr() as *mut u8, len * size_of::<T>());
+            file.pop(bytes)?;
+        }
+        self.tail.store(end, Ordering::Release);
+        if file.len() == 0 {
+            // Releases the final tail to readers observing the end of the stream
+            spill.spilled.store(false, Ordering::Release);
+        }
+        drop(file);
+
+        // Once the spilled data is exhausted, readers waiting for more data can observe the end
+        // of the stream.
+        if self.is_finished() {
+            self.wake_readers(None);
+        } else {
+            self.wake_readers(Some(end));
+        }
+        Ok(())
+    }
+
+    // Ask the refill thread to move spilled data back into the buffer.
+    fn request_refill(&self) {
+        if let Some(refiller) = self.spill.as_ref().and_then(|spill| spill.refiller.get()) {
+            refiller.unpark();
+        }
+    }
+}
+
+// Move spilled data back into the buffer as the readers free space, until no more data will be
+// read.
+//
+// This runs on its own thread so readers never block on file I/O while polling.
+fn refill_spilled<T>(state: Arc<State<T>>) {
+    let spill = state.spill.as_ref().unwrap();
+    loop {
+        if state.refill().is_err() {
+            // The spilled data can't be recovered, so end the stream early.
+            spill.spilled.store(false, Ordering::Release);
+            state.closed.store(true, Ordering::Release);
+            state.wake_readers(None);
+            return;
+        }
+
+        if spill.abandoned.load(Ordering::Relaxed) || state.is_finished() {
+            return;
+        }
+        std::thread::park();
     }
 }
 
 /// The writer of a circular buffer.
 ///
 /// Writes made to this become available at the associated [`Source`].
+///
+/// A writer waiting for a grant is only woken once the readers free enough space for it, or the
+/// readers are dropped.
 pub struct Sink<T> {
     state: Arc<State<T>>,
     tail: u64,
     available: usize,
-    read_waker: Option<Box<dyn Fn() + Send + Sync>>,
+    staging: Option<Staging<T>>,
+}
+
+// The sink is never structurally pinned.
+// This is required because `Vec<T>` in `Staging` is only `Unpin` if `T` is.
+impl<T> Unpin for Sink<T> {}
+
+/// Writer-side buffer used while data is spilled.
+struct Staging<T> {
+    buffer: Vec<T>,
+    spilling: bool,
+    default: fn() -> T,
+}
+
+// Safety: only use with buffers created with `T: Copy`
+unsafe fn as_bytes<T>(slice: &[T]) -> &[u8] {
+    std::slice::from_raw_parts(slice.as_ptr() as *const u8, std::mem::size_of_val(slice))
+}
+
+// Safety: only use with buffers created with `T: Copy`
+unsafe fn copy_elements<T>(dst: &mut [T], src: &[T]) {
+    assert_eq!(dst.len(), src.len());
+    std::ptr::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr(), src.len())
 }
 
 impl<T> Sink<T> {
-    fn new(state: Arc<State<T>>) -> Self {
+    fn new(state: Arc<State<T>>, staging: Option<Staging<T>>) -> Self {
         Self {
             state,
             tail: 0,
             available: 0,
-            read_waker: None,
+            staging,
         }
     }
 
-    fn wake_readers(&mut self) {
-        if self.read_waker.is_none() {
-            let mut lock = self
-                .state
-                .read_waker
-                .lock()
-                .expect("another thread panicked");
-            std::mem::swap(&mut *lock, &mut self.read_waker);
+    fn is_spilling(&self) -> bool {
+        matches!(self.staging.as_ref(), Some(staging) if staging.spilling)
+    }
+
+    // Grant at least `count` elements, spilling if the buffer doesn't have enough space.
+    fn spilling_grant(&mut self, count: usize) {
+        let staging = self.staging.as_mut().unwrap();
+        let spill = self.state.spill.as_ref().unwrap();
+
+        // The stream ended early, so the writer can't write anything else.
+        if spill.failed.load(Ordering::Relaxed) {
+            staging.buffer.clear();
+            self.available = 0;
+            return;
         }
-        if let Some(read_waker) = self.read_waker.as_ref() {
-            read_waker()
+
+        if !staging.spilling {
+            if self.available >= count {
+                return;
+            }
+
+            // There's no reason to spill if the readers are gone.
+            let available = self.state.writeable_len();
+            if available >= count || self.state.closed.load(Ordering::Acquire) {
+                self.available = available;
+                return;
+            }
+
+            // Start spilling, preserving the current view.
+            staging.buffer.resize_with(self.available, staging.default);
+            // Safety: this region is owned exclusively by the writer.
+            unsafe {
+                let view = self.state.buffer.range(self.tail, self.available);
+                copy_elements(&mut staging.buffer, view);
+            }
+            staging.spilling = true;
+        } else {
+            self.state.request_refill();
+
+            // Check if the buffer has caught up, and stop spilling if possible.
+            let file = spill.file.lock().expect("another thread panicked");
+            if file.len() == 0 {
+                self.tail = self.state.tail.load(Ordering::Acquire);
+                let available = self.state.writeable_len();
+                if available >= count.max(staging.buffer.len()) {
+                    // Safety: this region is owned exclusively by the writer while not spilling.
+                    unsafe {
+                        let view = self.state.buffer.range_mut(self.tail, staging.buffer.len());
+                        copy_elements(view, &staging.buffer);
+                    }
+                    staging.buffer.clear();
+                    staging.spilling = false;
+                    self.available = available;
+                    return;
+                }
+            }
         }
+
+        if staging.buffer.len() < count {
+            staging.buffer.resize_with(count, staging.default);
+        }
+        self.available = staging.buffer.len();
+    }
+
+    // Grant at least `count` elements, advancing past the oldest data if necessary.
+    fn overwriting_grant(&mut self, count: usize) {
+        let len: u64 = self.state.buffer.len().try_into().unwrap();
+        let mut head = self.state.head.load(Ordering::Acquire);
+        loop {
+            let available: usize = (len - (self.tail - head)).try_into().unwrap();
+            // There's no reason to overwrite data once the reader is gone.
+            if available >= count || self.state.closed.load(Ordering::Acquire) {
+                self.available = available;
+                return;
+            }
+
+            // Acquires the reader's accesses to data it released.
+            let dropped: u64 = (count - available).try_into().unwrap();
+            match self.state.head.compare_exchange_weak(
+                head,
+                head + dropped,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    // The reader may be copying the dropped data.
+                    // Together with the fence in `LossySource::copy`, if the reader copies any data
+                    // written after this, it observes the new head and discards the data.
+                    fence(Ordering::Release);
+                    self.available = count;
+                    return;
+                }
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    // Release `count` elements to the spill file.
+    fn spilling_release(&mut self, count: usize) {
+        let staging = self.staging.as_mut().unwrap();
+        let spill = self.state.spill.as_ref().unwrap();
+        // There's no reason to spill if the readers are gone.
+        if !self.state.closed.load(Ordering::Acquire) {
+            let mut file = spill.file.lock().expect("another thread panicked");
+
+            // Safety: spilling buffers contain `Copy` elements
+            let data = unsafe { as_bytes(&staging.buffer[..count]) };
+            if file.push(data).is_err() {
+                // Later data can't follow the lost data, so end the stream early, like the refill
+                // thread does when it fails to read.
+                drop(file);
+                spill.failed.store(true, Ordering::Relaxed);
+                self.state.closed.store(true, Ordering::Release);
+                self.state.wake_readers(None);
+                self.state.request_refill();
+                staging.buffer.clear();
+                self.available = 0;
+                return;
+            }
+            spill.spilled.store(true, Ordering::Release);
+        }
+
+        staging.buffer.drain(..count);
+        self.available -= count;
+        self.state.request_refill();
     }
 }
 
 impl<T> Drop for Sink<T> {
     fn drop(&mut self) {
-        self.state.closed.store(true, Ordering::Relaxed);
-        self.wake_readers(); // waiting readers can exit without sufficient data
+        self.state.closed.store(true, Ordering::Release);
+        self.state.wake_readers(None); // waiting readers can exit without sufficient data
+        self.state.request_refill();
     }
 }
 
@@ -176,6 +546,10 @@ impl<T> View for Sink<T> {
     type Error = GrantOverflow;
 
     fn view(&self) -> &[T] {
+        if self.is_spilling() {
+            return &self.staging.as_ref().unwrap().buffer;
+        }
+
         // Safety: this region is owned exclusively by the writer.
         unsafe { self.state.buffer.range(self.tail, self.available) }
     }
@@ -189,10 +563,20 @@ impl<T> View for Sink<T> {
             return Poll::Ready(Err(GrantOverflow(self.state.buffer.len())));
         }
 
+        if self.staging.is_some() {
+            self.spilling_grant(count);
+            return Poll::Ready(Ok(()));
+        }
+
         if self.available >= count {
             return Poll::Ready(Ok(()));
         }
 
+        if self.state.lossy {
+            self.overwriting_grant(count);
+            return Poll::Ready(Ok(()));
+        }
+
         // Perform double-checking on the amount of available data
         // The first check is efficient, but may spuriously fail.
         // The second check occurs after the `acquire` produced by registering the waker.
@@ -201,9 +585,17 @@ impl<T> View for Sink<T> {
             self.available = available;
             Poll::Ready(Ok(()))
         } else {
+            // Only wake once the readers free enough space for this grant.
+            // Together with the fence in `State::wake_writer`, either the writer observes the new
+            // head or the readers observe the new wake position.
+            let len: u64 = self.state.buffer.len().try_into().unwrap();
+            let wake_at = (self.tail + u64::try_from(count).unwrap()).saturating_sub(len);
+            self.state.write_wake_at.store(wake_at, Ordering::Relaxed);
             self.state.write_waker.register(cx.waker());
+            fence(Ordering::SeqCst);
+
             let available = self.state.writeable_len();
-            if available >= count || self.state.closed.load(Ordering::Relaxed) {
+            if available >= count || self.state.closed.load(Ordering::Acquire) {
                 self.available = available;
                 Poll::Ready(Ok(()))
             } else {
@@ -212,21 +604,31 @@ impl<T> View for Sink<T> {
         }
     }
 
-    fn try_grant(self: &mut Self, count: usize) -> Result<bool, Self::Error> {
+    fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
         if count > self.state.buffer.len() {
             return Err(GrantOverflow(self.state.buffer.len()));
         }
 
+        if self.staging.is_some() {
+            self.spilling_grant(count);
+            return Ok(true);
+        }
+
         if self.available >= count {
             return Ok(true);
         }
 
+        if self.state.lossy {
+            self.overwriting_grant(count);
+            return Ok(true);
+        }
+
         let available = self.state.writeable_len();
         if available >= count {
             self.available = available;
-            return Ok(true);
+            Ok(true)
         } else {
-            return Ok(false)
+            Ok(false)
         }
     }
 
@@ -240,17 +642,27 @@ impl<T> View for Sink<T> {
             "attempted to release more than current grant"
         );
 
+        if self.is_spilling() {
+            self.spilling_release(count);
+            return;
+        }
+
         // Advance the buffer
         self.available -= count;
         let count: u64 = count.try_into().unwrap();
         self.tail += count;
-        self.state.tail.store(self.tail, Ordering::Relaxed);
-        self.wake_readers();
+        // Publish the written data to the readers
+        self.state.tail.store(self.tail, Ordering::Release);
+        self.state.wake_readers(Some(self.tail));
     }
 }
 
 impl<T> ViewMut for Sink<T> {
     fn view_mut(&mut self) -> &mut [T] {
+        if self.is_spilling() {
+            return &mut self.staging.as_mut().unwrap().buffer;
+        }
+
         // Safety: this region is owned exclusively by the writer.
         unsafe { self.state.buffer.range_mut(self.tail, self.available) }
     }
@@ -271,8 +683,12 @@ impl<T> Source<T> {
 
 impl<T> Drop for Source<T> {
     fn drop(&mut self) {
-        self.state.closed.store(true, Ordering::Relaxed);
+        if let Some(spill) = self.state.spill.as_ref() {
+            spill.abandoned.store(true, Ordering::Relaxed);
+        }
+        self.state.closed.store(true, Ordering::Release);
         self.state.write_waker.wake();
+        self.state.request_refill();
     }
 }
 
@@ -281,18 +697,21 @@ unsafe impl<T> SplittableViewImpl for Source<T> {
     type Error = GrantOverflow;
 
     unsafe fn set_reader_waker(&self, waker: impl Fn() + Send + Sync + 'static) {
-        let mut lock = self
-            .state
-            .read_waker
-            .lock()
-            .expect("another thread panicked");
-        assert!(lock.is_none(), "reader waker already set!");
-        *lock = Some(Box::new(waker));
+        self.state.set_read_waker(move |_| waker());
+    }
+
+    unsafe fn set_reader_waker_with_end(
+        &self,
+        waker: impl Fn(Option<u64>) + Send + Sync + 'static,
+    ) {
+        self.state.set_read_waker(waker);
     }
 
     unsafe fn set_head(&self, index: u64) {
-        self.state.head.store(index, Ordering::Relaxed);
-        self.state.write_waker.wake();
+        // Release the data before the head to the writer
+        self.state.head.store(index, Ordering::Release);
+        self.state.wake_writer(index);
+        self.state.request_refill();
     }
 
     unsafe fn compare_set_head(&self, index: u64) {
@@ -302,7 +721,7 @@ unsafe impl<T> SplittableViewImpl for Source<T> {
             while let Err(previous) = self.state.head.compare_exchange_weak(
                 current,
                 index,
-                Ordering::Relaxed,
+                Ordering::Release,
                 Ordering::Relaxed,
             ) {
                 if index > previous {
@@ -312,7 +731,9 @@ unsafe impl<T> SplittableViewImpl for Source<T> {
                 }
             }
         }
-        self.state.write_waker.wake();
+        // If another reader set a later head, it wakes the writer instead.
+        self.state.wake_writer(index);
+        self.state.request_refill();
     }
 
     fn poll_available(
@@ -336,19 +757,18 @@ unsafe impl<T> SplittableViewImpl for Source<T> {
         } else {
             register_wakeup(cx.waker());
             let available = self.state.readable_len(index);
-            if available >= len || self.state.closed.load(Ordering::Relaxed) {
+            if available >= len {
                 Poll::Ready(Ok(available))
+            } else if self.state.is_finished() {
+                // The writer may have released more data before closing the stream.
+                Poll::Ready(Ok(self.state.readable_len(index)))
             } else {
                 Poll::Pending
             }
         }
     }
 
-    fn try_available(
-        &self,
-        index: u64,
-        len: usize,
-    ) -> Result<usize, GrantOverflow> {
+    fn try_available(&self, index: u64, len: usize) -> Result<usize, GrantOverflow> {
         let max_len = self.state.buffer.len();
         if len > max_len {
             return Err(GrantOverflow(max_len));
@@ -356,13 +776,12 @@ unsafe impl<T> SplittableViewImpl for Source<T> {
 
         let available = self.state.readable_len(index);
         if available >= len {
-            return Ok(available);
+            Ok(available)
         } else {
-            return Ok(0)
+            Ok(0)
         }
     }
 
-
     unsafe fn view(&self, index: u64, len: usize) -> &[Self::Item] {
         self.state.buffer.range(index, len)
     }
@@ -374,6 +793,216 @@ unsafe impl<T> SplittableViewImplMut for Source<T> {
     }
 }
 
+/// An error produced by a [`LossySource`].
+#[derive(Copy, Clone, Debug)]
+pub enum LossyError {
+    /// A request was too large to grant.
+    GrantOverflow(GrantOverflow),
+
+    /// The reader fell behind the writer, and the oldest data was overwritten.
+    ///
+    /// Contains the number of elements lost.
+    /// The reader continues from the oldest retained element.
+    Overrun(u64),
+}
+
+impl From<GrantOverflow> for LossyError {
+    fn from(e: GrantOverflow) -> Self {
+        Self::GrantOverflow(e)
+    }
+}
+
+impl core::fmt::Display for LossyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::GrantOverflow(e) => e.fmt(f),
+            Self::Overrun(lost) => write!(f, "reader overrun, `{}` elements lost", lost),
+        }
+    }
+}
+
+impl std::error::Error for LossyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::GrantOverflow(e) => Some(e),
+            Self::Overrun(_) => None,
+        }
+    }
+}
+
+impl From<LossyError> for std::io::Error {
+    fn from(e: LossyError) -> Self {
+        let kind = match e {
+            LossyError::GrantOverflow(e) => return e.into(),
+            LossyError::Overrun(_) => std::io::ErrorKind::Other,
+        };
+        Self::new(kind, e)
+    }
+}
+
+/// The reader of a lossy circular buffer.
+///
+/// Writes made to the associated [`Sink`] are made available to this, unless they are
+/// overwritten first.
+///
+/// Granted data is copied out of the buffer, so the writer never waits for this reader, even
+/// while it holds a grant.
+/// If data is lost, the next grant produces [`LossyError::Overrun`] and continues from the oldest
+/// retained data.
+/// Any data still in the view when the overrun is detected is discarded, and counted as lost.
+pub struct LossySource<T> {
+    state: Arc<State<T>>,
+    waker: Arc<AtomicWaker>,
+    copied: Vec<T>, // data copied out of the buffer, never longer than the buffer
+    start: usize,   // start of the view in `copied`
+    next: u64,      // index of the first element that hasn't been copied
+}
+
+// The reader is never structurally pinned.
+// This is required because `Vec<T>` is only `Unpin` if `T` is.
+impl<T> Unpin for LossySource<T> {}
+
+impl<T: Copy> LossySource<T> {
+    fn new(state: Arc<State<T>>) -> Self {
+        let waker = Arc::new(AtomicWaker::new());
+        {
+            let waker = waker.clone();
+            state.set_read_waker(move |_| waker.wake());
+        }
+        let copied = Vec::with_capacity(state.buffer.len());
+        Self {
+            state,
+            waker,
+            copied,
+            start: 0,
+            next: 0,
+        }
+    }
+
+    // Index of the first element of the view.
+    fn head(&self) -> u64 {
+        self.next - u64::try_from(self.copied.len() - self.start).unwrap()
+    }
+
+    // Copy data out of the buffer, returning false if fewer than `count` elements are available.
+    fn copy(&mut self, count: usize) -> Result<bool, LossyError> {
+        // The view is never longer than the buffer, so this never allocates.
+        self.copied.drain(..self.start);
+        self.start = 0;
+
+        // Closing the stream releases the final tail, so check it first.
+        let finished = self.state.is_finished();
+        let tail = self.state.tail.load(Ordering::Acquire);
+        let start = self.next.max(self.state.head.load(Ordering::Acquire));
+        let len = usize::try_from(tail.saturating_sub(start))
+            .unwrap()
+            .min(self.copied.capacity() - self.copied.len());
+
+        // Safety: the writer may overwrite this data while it's copied, so it's read volatile and
+        // any elements it may have overwritten are discarded below.
+        // `T: Copy`, so discarded elements don't need to be dropped.
+        let copied = self.copied.len();
+        unsafe {
+            let src = self.state.buffer.ptr(start);
+            for i in 0..len {
+                self.copied.push(std::ptr::read_volatile(src.add(i)));
+            }
+        }
+
+        // The writer advances the head before overwriting data, with a release fence between.
+        // If any copied element was overwritten, this observes the advanced head.  The copy isn't
+        // atomic, so nothing orders a plain load after the writer's advance; read-modify-write
+        // operations always read the latest value.
+        fence(Ordering::Acquire);
+        let retained = start.max(self.state.head.fetch_add(0, Ordering::Relaxed));
+        let head = self.next - u64::try_from(copied).unwrap();
+        self.next = (start + u64::try_from(len).unwrap()).max(retained);
+        if retained > head + u64::try_from(copied).unwrap() {
+            // Data was lost after the view, so the view is discarded too.
+            let discarded = copied + usize::try_from(retained - start).unwrap().min(len);
+            self.copied.drain(..discarded);
+            return Err(LossyError::Overrun(retained - head));
+        }
+
+        Ok(self.copied.len() >= count || finished)
+    }
+}
+
+impl<T> Drop for LossySource<T> {
+    fn drop(&mut self) {
+        // The writer stops overwriting data once the reader is gone.
+        self.state.closed.store(true, Ordering::Release);
+    }
+}
+
+impl<T: Copy> View for LossySource<T> {
+    type Item = T;
+    type Error = LossyError;
+
+    fn view(&self) -> &[T] {
+        &self.copied[self.start..]
+    }
+
+    fn poll_grant(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        count: usize,
+    ) -> Poll<Result<(), LossyError>> {
+        let max_len = self.state.buffer.len();
+        if count > max_len {
+            return Poll::Ready(Err(GrantOverflow(max_len).into()));
+        }
+
+        if self.copied.len() - self.start >= count {
+            return Poll::Ready(Ok(()));
+        }
+
+        // Perform double-checking on the amount of available data
+        // The first check is efficient, but may spuriously fail.
+        // The second check occurs after the `acquire` produced by registering the waker, so any
+        // data released after it wakes the reader.
+        // Data copied by the first check is kept, and an overrun detected by either check is
+        // reported immediately.
+        if self.copy(count)? {
+            return Poll::Ready(Ok(()));
+        }
+        self.waker.register(cx.waker());
+        if self.copy(count)? {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    }
+
+    fn try_grant(&mut self, count: usize) -> Result<bool, LossyError> {
+        let max_len = self.state.buffer.len();
+        if count > max_len {
+            return Err(GrantOverflow(max_len).into());
+        }
+
+        if self.copied.len() - self.start >= count {
+            return Ok(true);
+        }
+
+        self.copy(count)
+    }
+
+    fn release(&mut self, count: usize) {
+        if count == 0 {
+            return;
+        }
+
+        assert!(
+            count <= self.copied.len() - self.start,
+            "attempted to release more than current grant"
+        );
+
+        // Release the data to the writer, unless the writer already advanced past it.
+        self.start += count;
+        self.state.head.fetch_max(self.head(), Ordering::Release);
+    }
+}
+
 /// Create a circular buffer that can hold at least `min_size` elements.
 ///
 /// # Panics
@@ -383,7 +1012,76 @@ pub fn circular_buffer<T: Send + Sync + Default + 'static>(
 ) -> (Sink<T>, Source<T>) {
     assert!(min_size > 0, "`min_size` must be greater than 0");
 
-    let state = Arc::new(State::new(min_size));
+    let state = Arc::new(State::new(min_size, None, false));
+
+    (Sink::new(state.clone(), None), Source::new(state))
+}
+
+/// Create a circular buffer that can hold at least `min_size` elements in memory, spilling to a
+/// temporary file when full.
+///
+/// Unlike [`circular_buffer`], the [`Sink`] never waits for the readers.
+/// If the buffer doesn't have enough space for a grant, released data is written to a temporary
+/// file instead.
+/// Readers receive spilled data in order, after it's moved back into the buffer.
+///
+/// Spilled data is moved back into the buffer by a dedicated thread, so readers never block on
+/// file I/O.
+/// The writer writes to the file while spilling, and may block on it.
+/// If the spill file can't be read or written, the stream ends early.
+/// Once the writer fails to write the spill file, its grants are empty, as if the readers were
+/// dropped.
+///
+/// # Errors
+/// Returns an error if the temporary file can't be created or the thread can't be spawned.
+///
+/// # Panics
+/// Panics if `min_size` is 0.
+pub fn spilling_circular_buffer<T: Copy + Send + Sync + Default + 'static>(
+    min_size: usize,
+) -> std::io::Result<(Sink<T>, Source<T>)> {
+    assert!(min_size > 0, "`min_size` must be greater than 0");
+
+    let state = Arc::new(State::new(min_size, Some(SpillFile::new()?), false));
+    let refiller = {
+        let state = state.clone();
+        std::thread::Builder::new()
+            .name("rivulet-spill".into())
+            .spawn(move || refill_spilled(state))?
+    };
+    // The refill thread refills once before parking, so it can't miss an earlier request.
+    let _ = state
+        .spill
+        .as_ref()
+        .unwrap()
+        .refiller
+        .set(refiller.thread().clone());
+    let staging = Staging {
+        buffer: Vec::new(),
+        spilling: false,
+        default: T::default,
+    };
+
+    Ok((Sink::new(state.clone(), Some(staging)), Source::new(state)))
+}
+
+/// Create a circular buffer that can hold at least `min_size` elements, overwriting the oldest
+/// data when full.
+///
+/// Unlike [`circular_buffer`], the [`Sink`] never waits for the reader.
+/// If the buffer doesn't have enough space for a grant, the oldest data is dropped instead.
+/// The reader detects dropped data on its next grant, which returns [`LossyError::Overrun`].
+///
+/// The [`LossySource`] copies granted data out of the buffer, so elements must be `Copy`.
+///
+/// # Panics
+/// Panics if `min_size` is 0.
+pub fn lossy_circular_buffer<T: Copy + Send + Sync + Default + 'static>(
+    min_size: usize,
+) -> (Sink<T>, LossySource<T>) {
+    assert!(min_size > 0, "`min_size` must be greater than 0");
+
+    let state = Arc::new(State::new(min_size, None, true));
 
-    (Sink::new(state.clone()), Source::new(state))
+    (Sink::new(state.clone(), None), LossySource::new(state))
 }
@@ -0,0 +1,181 @@
+#![cfg(feature = "std")]
+#![cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
+//! A single-threaded driver for running stages without an async runtime.
+//!
+//! The [`Driver`] polls a set of futures in round-robin order on the current thread.
+//! Only stages that have been woken are polled, and the thread is parked while no stages are
+//! ready, so a pipeline can be run in a plain thread or a test.
+//!
+//! # Example
+//! ```
+//! use rivulet::{circular_buffer, driver::Driver, SplittableView, View, ViewMut};
+//!
+//! let (mut sink, source) = circular_buffer::<u32>(4096);
+//! let mut source = source.into_view();
+//!
+//! let mut driver = Driver::new();
+//! driver.push(async move {
+//!     for i in 0..10000 {
+//!         sink.grant(1).await.unwrap();
+//!         sink.view_mut()[0] = i;
+//!         sink.release(1);
+//!     }
+//!     0
+//! });
+//! driver.push(async move {
+//!     let mut count = 0;
+//!     loop {
+//!         source.grant(1).await.unwrap();
+//!         let len = source.view().len();
+//!         if len == 0 {
+//!             break count;
+//!         }
+//!         count += len;
+//!         source.release(len);
+//!     }
+//! });
+//!
+//! let mut outputs = driver.collect::<Vec<_>>();
+//! outputs.sort();
+//! assert_eq!(outputs, [(0, 0), (1, 10000)]);
+//! ```
+
+use futures::task::{waker, ArcWake};
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    thread::Thread,
+};
+
+struct StageWaker {
+    ready: AtomicBool,
+    thread: Thread,
+}
+
+impl ArcWake for StageWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.ready.store(true, Ordering::Release);
+        arc_self.thread.unpark();
+    }
+}
+
+struct Stage<'a, T> {
+    future: Option<Pin<Box<dyn Future<Output = T> + 'a>>>,
+    waker: Arc<StageWaker>,
+}
+
+/// Runs a set of stages on the current thread, without an async runtime.
+///
+/// The driver is an iterator over the index and output of each stage, in order of completion.
+/// Iterating blocks the current thread until a stage completes.
+pub struct Driver<'a, T = ()> {
+    stages: Vec<Stage<'a, T>>,
+    completed: VecDeque<(usize, T)>,
+    remaining: usize,
+    thread: Thread,
+}
+
+impl<'a, T> Default for Driver<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T> Driver<'a, T> {
+    /// Create a new driver with no stages.
+    ///
+    /// The driver parks the thread it is created on while it waits for stages to be woken.
+    pub fn new() -> Self {
+        Self {
+            stages: Vec::new(),
+            completed: VecDeque::new(),
+            remaining: 0,
+            thread: std::thread::current(),
+        }
+    }
+
+    /// Add a stage to the driver, returning its index.
+    pub fn push(&mut self, stage: impl Future<Output = T> + 'a) -> usize {
+        self.stages.push(Stage {
+            future: Some(Box::pin(stage)),
+            waker: Arc::new(StageWaker {
+                ready: AtomicBool::new(true),
+                thread: self.thread.clone(),
+            }),
+        });
+        self.remaining += 1;
+        self.stages.len() - 1
+    }
+
+    /// Return the number of stages that haven't completed.
+    pub fn len(&self) -> usize {
+        self.remaining
+    }
+
+    /// Return true if every stage has completed.
+    pub fn is_empty(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Poll each woken stage once, in order.
+    ///
+    /// Returns the number of stages polled.
+    /// Completed stages are returned by [`try_next`](`Self::try_next`) and
+    /// [`next`](`Iterator::next`).
+    pub fn turn(&mut self) -> usize {
+        let mut polled = 0;
+        for (index, stage) in self.stages.iter_mut().enumerate() {
+            let future = match stage.future.as_mut() {
+                Some(future) => future,
+                None => continue,
+            };
+            if !stage.waker.ready.swap(false, Ordering::Acquire) {
+                continue;
+            }
+
+            polled += 1;
+            let waker = waker(stage.waker.clone());
+            if let Poll::Ready(output) = future.as_mut().poll(&mut Context::from_waker(&waker)) {
+                stage.future = None;
+                self.remaining -= 1;
+                self.completed.push_back((index, output));
+            }
+        }
+        polled
+    }
+
+    /// Return the index and output of a completed stage, without blocking.
+    ///
+    /// If no completed stages are waiting to be returned, the woken stages are polled once.
+    pub fn try_next(&mut self) -> Option<(usize, T)> {
+        if self.completed.is_empty() {
+            self.turn();
+        }
+        self.completed.pop_front()
+    }
+}
+
+impl<'a, T> Iterator for Driver<'a, T> {
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(completed) = self.completed.pop_front() {
+                return Some(completed);
+            }
+            if self.remaining == 0 {
+                return None;
+            }
+            if self.turn() == 0 {
+                // Wakers unpark this thread, so a wake between polling and parking isn't missed.
+                std::thread::park();
+            }
+        }
+    }
+}
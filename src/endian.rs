@@ -0,0 +1,286 @@
+#![cfg(feature = "std")]
+#![cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
+//! Decoding and encoding samples with a particular byte order.
+//!
+//! Unlike [`cast`](`crate::cast`), these adapters copy each grant into a separate buffer, so the
+//! underlying stream may have any alignment or byte order.
+
+use crate::{cast::Pod, View, ViewMut};
+use std::{
+    mem::size_of,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The order of bytes in a sample.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// Most significant byte first.
+    Big,
+    /// Least significant byte first.
+    Little,
+}
+
+impl Endianness {
+    /// The byte order of the target platform.
+    pub const NATIVE: Self = if cfg!(target_endian = "big") {
+        Self::Big
+    } else {
+        Self::Little
+    };
+}
+
+/// Samples that can be decoded from bytes.
+pub trait Sample: Pod + Unpin {
+    /// Reverse the byte order of the sample.
+    fn swap_bytes(self) -> Self;
+}
+
+macro_rules! impl_sample_int {
+    { $($type:ty),* } => {
+        $(
+        impl Sample for $type {
+            fn swap_bytes(self) -> Self {
+                <$type>::swap_bytes(self)
+            }
+        }
+        )*
+    }
+}
+
+impl_sample_int! { u8, u16, u32, u64, u128, i8, i16, i32, i64, i128 }
+
+impl Sample for f32 {
+    fn swap_bytes(self) -> Self {
+        Self::from_bits(self.to_bits().swap_bytes())
+    }
+}
+
+impl Sample for f64 {
+    fn swap_bytes(self) -> Self {
+        Self::from_bits(self.to_bits().swap_bytes())
+    }
+}
+
+impl<T: Sample, const N: usize> Sample for [T; N] {
+    fn swap_bytes(self) -> Self {
+        self.map(T::swap_bytes)
+    }
+}
+
+// Convert between native byte order and `endianness`, in either direction.
+fn swap_if_needed<T: Sample>(value: T, endianness: Endianness) -> T {
+    if endianness == Endianness::NATIVE {
+        value
+    } else {
+        value.swap_bytes()
+    }
+}
+
+/// Decodes a view of bytes into a view of samples.
+///
+/// Grants and releases are made in whole samples.
+/// If the stream ends with a partial sample, the partial sample is never viewed.
+#[derive(Clone, Debug)]
+pub struct Decode<V, T> {
+    view: V,
+    endianness: Endianness,
+    decoded: Vec<T>,
+    start: usize, // index of the first unreleased sample in `decoded`
+}
+
+impl<V, T> Decode<V, T>
+where
+    V: View<Item = u8>,
+    T: Sample,
+{
+    /// Create a new decoding view.
+    ///
+    /// # Panics
+    /// Panics if `T` is zero-sized.
+    pub fn new(view: V, endianness: Endianness) -> Self {
+        assert!(size_of::<T>() > 0, "zero-sized types are not supported");
+        Self {
+            view,
+            endianness,
+            decoded: Vec::new(),
+            start: 0,
+        }
+    }
+
+    /// Return the original view.
+    pub fn into_inner(self) -> V {
+        self.view
+    }
+
+    /// Decode any samples in the current view that haven't been decoded yet.
+    fn decode(&mut self) {
+        // Released samples are only removed here, so a grant moves them at most once.
+        self.decoded.drain(..self.start);
+        self.start = 0;
+
+        let bytes = self.view.view();
+        let len = bytes.len() / size_of::<T>();
+        for chunk in bytes[self.decoded.len() * size_of::<T>()..len * size_of::<T>()]
+            .chunks_exact(size_of::<T>())
+        {
+            // Safety: `T` is valid for any bytes
+            let value = unsafe { std::ptr::read_unaligned(chunk.as_ptr() as *const T) };
+            self.decoded.push(swap_if_needed(value, self.endianness));
+        }
+    }
+}
+
+impl<V, T> View for Decode<V, T>
+where
+    V: View<Item = u8>,
+    T: Sample,
+{
+    type Item = T;
+    type Error = V::Error;
+
+    fn view(&self) -> &[T] {
+        &self.decoded[self.start..]
+    }
+
+    fn poll_grant(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        count: usize,
+    ) -> Poll<Result<(), Self::Error>> {
+        futures::ready!(
+            Pin::new(&mut self.view).poll_grant(cx, count.saturating_mul(size_of::<T>()))
+        )?;
+        self.decode();
+        Poll::Ready(Ok(()))
+    }
+
+    fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
+        let granted = self.view.try_grant(count.saturating_mul(size_of::<T>()))?;
+        self.decode();
+        Ok(granted)
+    }
+
+    fn release(&mut self, count: usize) {
+        assert!(
+            count <= self.decoded.len() - self.start,
+            "attempted to release more than current grant"
+        );
+        self.view.release(count * size_of::<T>());
+        self.start += count;
+    }
+}
+
+/// Encodes a view of samples into a view of bytes.
+///
+/// Samples are encoded when released.
+#[derive(Clone, Debug)]
+pub struct Encode<V, T> {
+    view: V,
+    endianness: Endianness,
+    buffer: Vec<T>,
+    start: usize, // index of the first unreleased sample in `buffer`
+}
+
+impl<V, T> Encode<V, T>
+where
+    V: ViewMut<Item = u8>,
+    T: Sample,
+{
+    /// Create a new encoding view.
+    ///
+    /// # Panics
+    /// Panics if `T` is zero-sized.
+    pub fn new(view: V, endianness: Endianness) -> Self {
+        assert!(size_of::<T>() > 0, "zero-sized types are not supported");
+        Self {
+            view,
+            endianness,
+            buffer: Vec::new(),
+            start: 0,
+        }
+    }
+
+    /// Return the original view.
+    pub fn into_inner(self) -> V {
+        self.view
+    }
+
+    /// Extend the buffer to cover the current view.
+    fn extend(&mut self) {
+        // Released samples are only removed here, so a grant moves them at most once.
+        self.buffer.drain(..self.start);
+        self.start = 0;
+
+        let len = self.view.view().len() / size_of::<T>();
+        if self.buffer.len() < len {
+            // Safety: `T` is valid for any bytes
+            self.buffer
+                .resize_with(len, || unsafe { std::mem::zeroed() });
+        }
+    }
+}
+
+impl<V, T> View for Encode<V, T>
+where
+    V: ViewMut<Item = u8>,
+    T: Sample,
+{
+    type Item = T;
+    type Error = V::Error;
+
+    fn view(&self) -> &[T] {
+        &self.buffer[self.start..]
+    }
+
+    fn poll_grant(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        count: usize,
+    ) -> Poll<Result<(), Self::Error>> {
+        futures::ready!(
+            Pin::new(&mut self.view).poll_grant(cx, count.saturating_mul(size_of::<T>()))
+        )?;
+        self.extend();
+        Poll::Ready(Ok(()))
+    }
+
+    fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
+        let granted = self.view.try_grant(count.saturating_mul(size_of::<T>()))?;
+        self.extend();
+        Ok(granted)
+    }
+
+    fn release(&mut self, count: usize) {
+        assert!(
+            count <= self.buffer.len() - self.start,
+            "attempted to release more than current grant"
+        );
+        let bytes = &mut self.view.view_mut()[..count * size_of::<T>()];
+        let values = &self.buffer[self.start..self.start + count];
+        for (chunk, value) in bytes
+            .chunks_exact_mut(size_of::<T>())
+            .zip(values.iter().copied())
+        {
+            // Safety: the chunk is the size of `T`
+            unsafe {
+                std::ptr::write_unaligned(
+                    chunk.as_mut_ptr() as *mut T,
+                    swap_if_needed(value, self.endianness),
+                )
+            };
+        }
+        self.view.release(count * size_of::<T>());
+        self.start += count;
+    }
+}
+
+impl<V, T> ViewMut for Encode<V, T>
+where
+    V: ViewMut<Item = u8>,
+    T: Sample,
+{
+    fn view_mut(&mut self) -> &mut [T] {
+        &mut self.buffer[self.start..]
+    }
+}
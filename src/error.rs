@@ -25,3 +25,163 @@ impl std::convert::From<GrantOverflow> for std::io::Error {
         Self::new(std::io::ErrorKind::InvalidInput, e)
     }
 }
+
+/// Errors that may be caused by a request that was too large to grant.
+///
+/// Adapters use this to fall back to smaller grants when the maximum grant of a stream isn't
+/// known in advance.
+pub trait AsGrantOverflow {
+    /// Return the [`GrantOverflow`], if this error was caused by one.
+    fn as_grant_overflow(&self) -> Option<GrantOverflow>;
+}
+
+impl AsGrantOverflow for GrantOverflow {
+    fn as_grant_overflow(&self) -> Option<GrantOverflow> {
+        Some(*self)
+    }
+}
+
+impl AsGrantOverflow for core::convert::Infallible {
+    fn as_grant_overflow(&self) -> Option<GrantOverflow> {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsGrantOverflow for std::io::Error {
+    fn as_grant_overflow(&self) -> Option<GrantOverflow> {
+        self.get_ref()?.downcast_ref().copied()
+    }
+}
+
+/// An error produced by any stream.
+///
+/// Errors produced by the streams in this crate can be converted to this type, so generic
+/// pipelines can use a single error type with [`View::err_into`](crate::View::err_into).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A request was too large to grant.
+    GrantOverflow(GrantOverflow),
+
+    /// The stream was aborted before it was completed.
+    Aborted,
+
+    /// A request timed out.
+    Timeout,
+
+    /// The reader fell behind the stream and was evicted.
+    ///
+    /// Produced when a lossy reader is overrun by its writer.
+    ReaderEvicted,
+
+    /// A frame or record exceeded the maximum length.
+    ///
+    /// Contains the length of the frame, or the length read before giving up on finding the end of
+    /// the record.
+    TooLong(u64),
+
+    /// The stream ended in the middle of a frame.
+    Truncated,
+
+    /// A frame's length prefix was malformed.
+    InvalidPrefix,
+
+    /// An I/O error occurred.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
+    Io(std::io::Error),
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::GrantOverflow(e) => e.fmt(f),
+            Self::Aborted => write!(f, "stream aborted"),
+            Self::Timeout => write!(f, "request timed out"),
+            Self::ReaderEvicted => write!(f, "reader evicted"),
+            Self::TooLong(len) => write!(f, "frame length `{}` exceeds maximum", len),
+            Self::Truncated => write!(f, "stream ended in the middle of a frame"),
+            Self::InvalidPrefix => write!(f, "invalid frame length prefix"),
+            #[cfg(feature = "std")]
+            Self::Io(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::GrantOverflow(e) => Some(e),
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<GrantOverflow> for Error {
+    fn from(e: GrantOverflow) -> Self {
+        Self::GrantOverflow(e)
+    }
+}
+
+impl From<core::convert::Infallible> for Error {
+    fn from(e: core::convert::Infallible) -> Self {
+        match e {}
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<crate::circular_buffer::LossyError> for Error {
+    fn from(e: crate::circular_buffer::LossyError) -> Self {
+        match e {
+            crate::circular_buffer::LossyError::GrantOverflow(e) => Self::GrantOverflow(e),
+            crate::circular_buffer::LossyError::Overrun(_) => Self::ReaderEvicted,
+        }
+    }
+}
+
+impl AsGrantOverflow for Error {
+    fn as_grant_overflow(&self) -> Option<GrantOverflow> {
+        match self {
+            Self::GrantOverflow(e) => Some(*e),
+            #[cfg(feature = "std")]
+            Self::Io(e) => e.as_grant_overflow(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsGrantOverflow for crate::circular_buffer::LossyError {
+    fn as_grant_overflow(&self) -> Option<GrantOverflow> {
+        match self {
+            Self::GrantOverflow(e) => Some(*e),
+            Self::Overrun(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::convert::From<Error> for std::io::Error {
+    fn from(e: Error) -> Self {
+        let kind = match e {
+            Error::GrantOverflow(e) => return e.into(),
+            Error::Io(e) => return e,
+            Error::Aborted => std::io::ErrorKind::ConnectionAborted,
+            Error::Timeout => std::io::ErrorKind::TimedOut,
+            Error::ReaderEvicted => std::io::ErrorKind::Other,
+            Error::TooLong(_) | Error::InvalidPrefix => std::io::ErrorKind::InvalidData,
+            Error::Truncated => std::io::ErrorKind::UnexpectedEof,
+        };
+        Self::new(kind, e)
+    }
+}
@@ -0,0 +1,227 @@
+#![cfg(feature = "std")]
+#![cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
+//! Memory-mapped files as streams.
+//!
+//! Files are mapped into memory without copying, and pages are only loaded by the operating system
+//! as they are accessed.
+
+use crate::splittable::{SplittableViewImpl, SplittableViewImplMut};
+use std::{
+    convert::{Infallible, TryInto},
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+use vmap::{AdviseAccess, AdviseUsage, Flush, Map, MapMut};
+
+fn map_error(e: vmap::Error) -> std::io::Error {
+    std::io::Error::new(e.kind(), e)
+}
+
+fn available(len: usize, index: u64) -> usize {
+    let index: usize = index.try_into().unwrap();
+    len - index.min(len)
+}
+
+/// Treats a read-only memory-mapped file as a stream.
+pub struct File {
+    map: Option<Map>,
+}
+
+// Safety: the mapping is never mutated.
+unsafe impl Send for File {}
+unsafe impl Sync for File {}
+
+impl File {
+    /// Map the file at `path` into memory.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Self::map(&file)
+    }
+
+    /// Map an open file into memory.
+    pub fn map(file: &std::fs::File) -> std::io::Result<Self> {
+        // Empty files can't be mapped, but are valid empty streams.
+        let map = if file.metadata()?.len() == 0 {
+            None
+        } else {
+            let map = Map::with_options().map(file).map_err(map_error)?;
+            map.advise(AdviseAccess::Sequential, AdviseUsage::Normal)
+                .map_err(map_error)?;
+            Some(map)
+        };
+        Ok(Self { map })
+    }
+
+    /// Return the length of the file.
+    pub fn len(&self) -> usize {
+        self.map.as_ref().map_or(0, |map| map.len())
+    }
+
+    /// Return true if the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+unsafe impl SplittableViewImpl for File {
+    type Item = u8;
+    type Error = Infallible;
+
+    unsafe fn set_reader_waker(&self, _: impl Fn() + Send + Sync + 'static) {}
+
+    unsafe fn set_head(&self, _: u64) {}
+
+    unsafe fn compare_set_head(&self, _: u64) {}
+
+    fn poll_available(
+        self: Pin<&Self>,
+        _cx: &mut Context,
+        _register_wakeup: impl FnOnce(&Waker),
+        index: u64,
+        len: usize,
+    ) -> Poll<Result<usize, Self::Error>> {
+        Poll::Ready(self.try_available(index, len))
+    }
+
+    fn try_available(&self, index: u64, _len: usize) -> Result<usize, Self::Error> {
+        Ok(available(self.len(), index))
+    }
+
+    unsafe fn view(&self, index: u64, len: usize) -> &[Self::Item] {
+        match self.map.as_ref() {
+            Some(map) => {
+                let index = index.try_into().unwrap();
+                &map[index..index + len]
+            }
+            None => &[],
+        }
+    }
+}
+
+/// Treats a writable memory-mapped file as a stream.
+///
+/// The file is preallocated, and the stream ends at the end of the file.
+///
+/// Modified data is written to the file when this is dropped, including after it's split into
+/// views.
+pub struct FileMut {
+    map: Option<MapMut>,
+    file: std::fs::File,
+    ptr: *mut u8,
+    len: usize,
+}
+
+// Safety: mutable views never overlap, so sharing this is no different than sharing `[u8]`.
+unsafe impl Send for FileMut {}
+unsafe impl Sync for FileMut {}
+
+impl FileMut {
+    /// Create a file at `path` with a length of `len` bytes, and map it into memory.
+    ///
+    /// If the file already exists, it is truncated.
+    pub fn create(path: impl AsRef<Path>, len: usize) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len(len.try_into().unwrap())?;
+        Self::map(file)
+    }
+
+    /// Map an existing file at `path` into memory.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)?;
+        Self::map(file)
+    }
+
+    /// Map an open file into memory.
+    ///
+    /// The file must be opened for reading and writing.
+    pub fn map(file: std::fs::File) -> std::io::Result<Self> {
+        // Empty files can't be mapped, but are valid empty streams.
+        let (map, ptr, len) = if file.metadata()?.len() == 0 {
+            (None, std::ptr::NonNull::dangling().as_ptr(), 0)
+        } else {
+            let mut map = MapMut::with_options().map(&file).map_err(map_error)?;
+            map.advise(AdviseAccess::Sequential, AdviseUsage::Normal)
+                .map_err(map_error)?;
+            let ptr = map.as_mut_ptr();
+            let len = map.len();
+            (Some(map), ptr, len)
+        };
+        Ok(Self {
+            map,
+            file,
+            ptr,
+            len,
+        })
+    }
+
+    /// Return the length of the file.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return true if the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Write any modified data to the file.
+    ///
+    /// This also happens when the file is dropped, but errors can only be handled here.
+    pub fn flush(&self) -> std::io::Result<()> {
+        if let Some(map) = self.map.as_ref() {
+            map.flush(&self.file, Flush::Sync).map_err(map_error)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for FileMut {
+    fn drop(&mut self) {
+        // Errors can't be reported here, and can be handled by calling `flush` before dropping.
+        let _ = self.flush();
+    }
+}
+
+unsafe impl SplittableViewImpl for FileMut {
+    type Item = u8;
+    type Error = Infallible;
+
+    unsafe fn set_reader_waker(&self, _: impl Fn() + Send + Sync + 'static) {}
+
+    unsafe fn set_head(&self, _: u64) {}
+
+    unsafe fn compare_set_head(&self, _: u64) {}
+
+    fn poll_available(
+        self: Pin<&Self>,
+        _cx: &mut Context,
+        _register_wakeup: impl FnOnce(&Waker),
+        index: u64,
+        len: usize,
+    ) -> Poll<Result<usize, Self::Error>> {
+        Poll::Ready(self.try_available(index, len))
+    }
+
+    fn try_available(&self, index: u64, _len: usize) -> Result<usize, Self::Error> {
+        Ok(available(self.len, index))
+    }
+
+    unsafe fn view(&self, index: u64, len: usize) -> &[Self::Item] {
+        std::slice::from_raw_parts(self.ptr.add(index.try_into().unwrap()), len)
+    }
+}
+
+unsafe impl SplittableViewImplMut for FileMut {
+    unsafe fn view_mut(&self, index: u64, len: usize) -> &mut [Self::Item] {
+        std::slice::from_raw_parts_mut(self.ptr.add(index.try_into().unwrap()), len)
+    }
+}
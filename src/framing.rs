@@ -0,0 +1,605 @@
+#![cfg(feature = "std")]
+#![cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
+//! Frames over streams of bytes.
+//!
+//! Length-delimited frames are a length prefix followed by that many bytes of payload.
+//! [`FrameReader`] reads frames from a [`View`], and [`FrameWriter`] writes them to a [`ViewMut`].
+//! Frames that fit in a single grant are accessed without copying.
+//! Longer frames are read by copying them into a buffer.
+//!
+//! Delimited records, such as lines of text, are read by [`DelimitedReader`] without copying.
+
+use crate::{
+    endian::Endianness,
+    error::{AsGrantOverflow, GrantOverflow},
+    View, ViewMut,
+};
+
+/// The encoding of a frame's length.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Prefix {
+    /// A 16-bit unsigned integer.
+    U16,
+    /// A 32-bit unsigned integer.
+    U32,
+    /// An unsigned LEB128 variable-length integer.
+    Varint,
+}
+
+impl Prefix {
+    /// The largest length representable by the prefix.
+    fn max_frame_len(self) -> u64 {
+        match self {
+            Self::U16 => u16::MAX.into(),
+            Self::U32 => u32::MAX.into(),
+            Self::Varint => u64::MAX,
+        }
+    }
+
+    /// The number of bytes in the prefix, when encoding `len`.
+    fn len(self, len: usize) -> usize {
+        match self {
+            Self::U16 => 2,
+            Self::U32 => 4,
+            Self::Varint => {
+                let mut bytes = 1;
+                let mut len = len >> 7;
+                while len > 0 {
+                    bytes += 1;
+                    len >>= 7;
+                }
+                bytes
+            }
+        }
+    }
+}
+
+const MAX_VARINT_LEN: usize = 10;
+
+/// Configuration for framed streams.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Config {
+    prefix: Prefix,
+    endianness: Endianness,
+    max_frame_len: usize,
+    max_grant: usize,
+}
+
+impl Config {
+    /// Create a new configuration using the specified length prefix.
+    ///
+    /// Fixed-size prefixes are big-endian by default, and frames may be any length that the
+    /// prefix can represent.
+    pub fn new(prefix: Prefix) -> Self {
+        Self {
+            prefix,
+            endianness: Endianness::Big,
+            max_frame_len: usize::MAX,
+            max_grant: usize::MAX,
+        }
+    }
+
+    /// Set the byte order of fixed-size prefixes.
+    pub fn endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Set the maximum length of a frame's payload.
+    ///
+    /// Longer frames produce [`Error::TooLong`].
+    pub fn max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// Set the maximum grant requested from the underlying stream.
+    ///
+    /// Frames that don't fit in a single grant are copied in pieces.
+    /// By default, the maximum grant is detected from the [`GrantOverflow`] produced by the
+    /// underlying stream when a frame doesn't fit.
+    pub fn max_grant(mut self, max_grant: usize) -> Self {
+        self.max_grant = max_grant;
+        self
+    }
+
+    /// The largest payload allowed by both the prefix and the maximum frame length.
+    fn frame_len_limit(&self) -> u64 {
+        (self.max_frame_len as u64).min(self.prefix.max_frame_len())
+    }
+
+    fn decode_fixed<const N: usize>(&self, bytes: &[u8]) -> [u8; N] {
+        let mut prefix = [0; N];
+        prefix.copy_from_slice(&bytes[..N]);
+        prefix
+    }
+
+    /// Encode the prefix for `len` into `bytes`, using all of `bytes`.
+    fn encode(&self, len: usize, bytes: &mut [u8]) {
+        match (self.prefix, self.endianness) {
+            (Prefix::U16, Endianness::Big) => bytes.copy_from_slice(&(len as u16).to_be_bytes()),
+            (Prefix::U16, Endianness::Little) => bytes.copy_from_slice(&(len as u16).to_le_bytes()),
+            (Prefix::U32, Endianness::Big) => bytes.copy_from_slice(&(len as u32).to_be_bytes()),
+            (Prefix::U32, Endianness::Little) => bytes.copy_from_slice(&(len as u32).to_le_bytes()),
+            (Prefix::Varint, _) => {
+                // Varints may be padded with continuation bytes to fill the reserved space.
+                let last = bytes.len() - 1;
+                let mut len = len;
+                for (i, byte) in bytes.iter_mut().enumerate() {
+                    *byte = (len & 0x7f) as u8;
+                    if i != last {
+                        *byte |= 0x80;
+                    }
+                    len >>= 7;
+                }
+            }
+        }
+    }
+}
+
+/// An error produced by a framed stream.
+#[derive(Debug)]
+pub enum Error<E> {
+    /// The underlying stream produced an error.
+    View(E),
+
+    /// A frame exceeded the maximum frame length.
+    ///
+    /// Contains the length of the frame, or for delimited records, the length read before giving
+    /// up on finding the delimiter.
+    TooLong(u64),
+
+    /// The stream ended in the middle of a frame.
+    Truncated,
+
+    /// A varint length prefix was malformed.
+    InvalidPrefix,
+}
+
+impl<E> From<E> for Error<E> {
+    fn from(e: E) -> Self {
+        Self::View(e)
+    }
+}
+
+impl<E> core::fmt::Display for Error<E>
+where
+    E: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::View(e) => e.fmt(f),
+            Self::TooLong(len) => write!(f, "frame length `{}` exceeds maximum", len),
+            Self::Truncated => write!(f, "stream ended in the middle of a frame"),
+            Self::InvalidPrefix => write!(f, "invalid frame length prefix"),
+        }
+    }
+}
+
+impl<E> std::error::Error for Error<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::View(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl<E> From<Error<E>> for crate::Error
+where
+    E: Into<crate::Error>,
+{
+    fn from(e: Error<E>) -> Self {
+        match e {
+            Error::View(e) => e.into(),
+            Error::TooLong(len) => Self::TooLong(len),
+            Error::Truncated => Self::Truncated,
+            Error::InvalidPrefix => Self::InvalidPrefix,
+        }
+    }
+}
+
+impl<E> From<Error<E>> for std::io::Error
+where
+    E: Into<std::io::Error>,
+{
+    fn from(e: Error<E>) -> Self {
+        let (kind, message) = match e {
+            Error::View(e) => return e.into(),
+            Error::TooLong(len) => (
+                std::io::ErrorKind::InvalidData,
+                format!("frame length `{}` exceeds maximum", len),
+            ),
+            Error::Truncated => (
+                std::io::ErrorKind::UnexpectedEof,
+                "stream ended in the middle of a frame".to_string(),
+            ),
+            Error::InvalidPrefix => (
+                std::io::ErrorKind::InvalidData,
+                "invalid frame length prefix".to_string(),
+            ),
+        };
+        Self::new(kind, message)
+    }
+}
+
+/// Grant at least `count` elements.
+///
+/// Returns `false` if the request exceeds the maximum grant of the stream, recording the maximum
+/// grant in `max_grant`.
+async fn grant_whole<V>(view: &mut V, count: usize, max_grant: &mut usize) -> Result<bool, V::Error>
+where
+    V: View,
+    V::Error: AsGrantOverflow,
+{
+    match view.grant(count).await {
+        Ok(()) => Ok(true),
+        Err(e) => match e.as_grant_overflow() {
+            Some(GrantOverflow(max)) if max < count => {
+                *max_grant = max;
+                Ok(false)
+            }
+            _ => Err(e),
+        },
+    }
+}
+
+/// Reads length-delimited frames from a stream of bytes.
+pub struct FrameReader<V> {
+    view: V,
+    config: Config,
+    consumed: usize,
+    buffer: Vec<u8>,
+}
+
+impl<V> FrameReader<V>
+where
+    V: View<Item = u8>,
+    V::Error: AsGrantOverflow,
+{
+    /// Create a new frame reader.
+    pub fn new(view: V, config: Config) -> Self {
+        Self {
+            view,
+            config,
+            consumed: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Return the original view, positioned after the last frame read.
+    pub fn into_inner(mut self) -> V {
+        self.release();
+        self.view
+    }
+
+    /// Release the previous frame.
+    fn release(&mut self) {
+        self.view.release(self.consumed);
+        self.consumed = 0;
+        self.buffer.clear();
+    }
+
+    /// Read the length prefix, returning the prefix length and frame length.
+    ///
+    /// Returns `None` if the stream ended cleanly before the prefix.
+    async fn read_prefix(&mut self) -> Result<Option<(usize, u64)>, Error<V::Error>> {
+        let fixed = match self.config.prefix {
+            Prefix::U16 => 2,
+            Prefix::U32 => 4,
+            Prefix::Varint => 0,
+        };
+        if fixed > 0 {
+            self.view.grant(fixed).await?;
+            let view = self.view.view();
+            if view.is_empty() {
+                return Ok(None);
+            } else if view.len() < fixed {
+                return Err(Error::Truncated);
+            }
+            let len = match (self.config.prefix, self.config.endianness) {
+                (Prefix::U16, Endianness::Big) => {
+                    u16::from_be_bytes(self.config.decode_fixed(view)).into()
+                }
+                (Prefix::U16, Endianness::Little) => {
+                    u16::from_le_bytes(self.config.decode_fixed(view)).into()
+                }
+                (_, Endianness::Big) => u32::from_be_bytes(self.config.decode_fixed(view)).into(),
+                (_, Endianness::Little) => {
+                    u32::from_le_bytes(self.config.decode_fixed(view)).into()
+                }
+            };
+            return Ok(Some((fixed, len)));
+        }
+
+        // Varints are read one byte at a time, so short frames at the end of a stream don't wait
+        // for data that may never arrive.
+        let mut len = 0u64;
+        for i in 0..MAX_VARINT_LEN {
+            self.view.grant(i + 1).await?;
+            let view = self.view.view();
+            if view.is_empty() {
+                return Ok(None);
+            } else if view.len() <= i {
+                return Err(Error::Truncated);
+            }
+            let byte = u64::from(view[i] & 0x7f);
+            if i == MAX_VARINT_LEN - 1 && byte > 1 {
+                return Err(Error::InvalidPrefix);
+            }
+            len |= byte << (7 * i);
+            if view[i] & 0x80 == 0 {
+                return Ok(Some((i + 1, len)));
+            }
+        }
+        Err(Error::InvalidPrefix)
+    }
+
+    /// Read the next frame.
+    ///
+    /// Returns `None` at the end of the stream.
+    /// The frame is released on the next read.
+    pub async fn next(&mut self) -> Result<Option<&[u8]>, Error<V::Error>> {
+        self.release();
+
+        let (prefix_len, len) = match self.read_prefix().await? {
+            Some(prefix) => prefix,
+            None => return Ok(None),
+        };
+        if len > self.config.frame_len_limit() {
+            return Err(Error::TooLong(len));
+        }
+        let len = len as usize;
+
+        // Frames that fit in one grant are viewed directly.
+        let total = prefix_len.saturating_add(len);
+        if total <= self.config.max_grant
+            && grant_whole(&mut self.view, total, &mut self.config.max_grant).await?
+        {
+            if self.view.view().len() < total {
+                return Err(Error::Truncated);
+            }
+            self.consumed = total;
+            return Ok(Some(&self.view.view()[prefix_len..total]));
+        }
+
+        self.view.release(prefix_len);
+        self.buffer.reserve(len);
+        let mut remaining = len;
+        while remaining > 0 {
+            self.view
+                .grant(remaining.min(self.config.max_grant))
+                .await?;
+            let view = self.view.view();
+            let count = view.len().min(remaining);
+            if count == 0 {
+                return Err(Error::Truncated);
+            }
+            self.buffer.extend_from_slice(&view[..count]);
+            self.view.release(count);
+            remaining -= count;
+        }
+        Ok(Some(&self.buffer))
+    }
+
+    /// Read the next frame, blocking the current thread.
+    pub fn blocking_next(&mut self) -> Result<Option<&[u8]>, Error<V::Error>> {
+        futures::executor::block_on(self.next())
+    }
+}
+
+/// Writes length-delimited frames to a stream of bytes.
+pub struct FrameWriter<V> {
+    view: V,
+    config: Config,
+    reserved: Option<(usize, usize)>,
+}
+
+impl<V> FrameWriter<V>
+where
+    V: ViewMut<Item = u8>,
+    V::Error: AsGrantOverflow,
+{
+    /// Create a new frame writer.
+    pub fn new(view: V, config: Config) -> Self {
+        Self {
+            view,
+            config,
+            reserved: None,
+        }
+    }
+
+    /// Return the original view, positioned after the last frame committed.
+    pub fn into_inner(self) -> V {
+        self.view
+    }
+
+    /// Check that a frame of `len` bytes is allowed.
+    fn check_len(&self, len: usize) -> Result<(), Error<V::Error>> {
+        if len as u64 > self.config.frame_len_limit() {
+            Err(Error::TooLong(len as u64))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Reserve space for a frame of up to `max_len` bytes, returning the space for the payload.
+    ///
+    /// After writing the payload, the frame is completed by [`commit`](`Self::commit`), which
+    /// fills in the length prefix.
+    /// The frame must fit in a single grant of the underlying stream.
+    ///
+    /// When using [`Prefix::Varint`], the prefix is sized for `max_len` and padded if the
+    /// committed frame is shorter.
+    pub async fn reserve(&mut self, max_len: usize) -> Result<&mut [u8], Error<V::Error>> {
+        self.check_len(max_len)?;
+        let prefix_len = self.config.prefix.len(max_len);
+        let total = prefix_len.saturating_add(max_len);
+        self.view.grant(total).await?;
+        if self.view.view().len() < total {
+            return Err(Error::Truncated);
+        }
+        self.reserved = Some((prefix_len, max_len));
+        Ok(&mut self.view.view_mut()[prefix_len..total])
+    }
+
+    /// Complete the reserved frame with a payload of `len` bytes.
+    ///
+    /// # Panics
+    /// Panics if no frame is reserved, or `len` exceeds the reserved length.
+    pub fn commit(&mut self, len: usize) {
+        let (prefix_len, max_len) = self
+            .reserved
+            .take()
+            .expect("attempted to commit without reserving a frame");
+        assert!(len <= max_len, "attempted to commit more than reserved");
+        self.config
+            .encode(len, &mut self.view.view_mut()[..prefix_len]);
+        self.view.release(prefix_len + len);
+    }
+
+    /// Write a complete frame.
+    ///
+    /// Frames that don't fit in a single grant are copied in pieces.
+    pub async fn write(&mut self, frame: &[u8]) -> Result<(), Error<V::Error>> {
+        self.reserved = None;
+        self.check_len(frame.len())?;
+        let prefix_len = self.config.prefix.len(frame.len());
+        let total = prefix_len.saturating_add(frame.len());
+        if total <= self.config.max_grant
+            && grant_whole(&mut self.view, total, &mut self.config.max_grant).await?
+        {
+            self.reserve(frame.len()).await?.copy_from_slice(frame);
+            self.commit(frame.len());
+            return Ok(());
+        }
+
+        self.view.grant(prefix_len).await?;
+        if self.view.view().len() < prefix_len {
+            return Err(Error::Truncated);
+        }
+        self.config
+            .encode(frame.len(), &mut self.view.view_mut()[..prefix_len]);
+        self.view.release(prefix_len);
+        let mut frame = frame;
+        while !frame.is_empty() {
+            self.view
+                .grant(frame.len().min(self.config.max_grant))
+                .await?;
+            let view = self.view.view_mut();
+            let count = view.len().min(frame.len());
+            if count == 0 {
+                return Err(Error::Truncated);
+            }
+            view[..count].copy_from_slice(&frame[..count]);
+            self.view.release(count);
+            frame = &frame[count..];
+        }
+        Ok(())
+    }
+
+    /// Reserve space for a frame, blocking the current thread.
+    pub fn blocking_reserve(&mut self, max_len: usize) -> Result<&mut [u8], Error<V::Error>> {
+        futures::executor::block_on(self.reserve(max_len))
+    }
+
+    /// Write a complete frame, blocking the current thread.
+    pub fn blocking_write(&mut self, frame: &[u8]) -> Result<(), Error<V::Error>> {
+        futures::executor::block_on(self.write(frame))
+    }
+}
+
+/// Reads records separated by a delimiter from a stream of bytes.
+///
+/// Each record is viewed without copying, by growing the grant until it contains the delimiter.
+pub struct DelimitedReader<V> {
+    view: V,
+    delimiter: u8,
+    max_len: usize,
+    max_grant: usize,
+    consumed: usize,
+}
+
+impl<V> DelimitedReader<V>
+where
+    V: View<Item = u8>,
+    V::Error: AsGrantOverflow,
+{
+    /// Create a new delimited reader, with records of up to `max_len` bytes.
+    ///
+    /// Records and their delimiter must fit in a single grant, so records that don't fit in the
+    /// underlying stream produce [`Error::TooLong`], even if they're shorter than `max_len`.
+    pub fn new(view: V, delimiter: u8, max_len: usize) -> Self {
+        Self {
+            view,
+            delimiter,
+            max_len,
+            max_grant: usize::MAX,
+            consumed: 0,
+        }
+    }
+
+    /// Return the original view, positioned after the last record read.
+    pub fn into_inner(mut self) -> V {
+        self.view.release(self.consumed);
+        self.view
+    }
+
+    /// Read the next record, not including the delimiter.
+    ///
+    /// Returns `None` at the end of the stream.
+    /// If the stream ends without a delimiter, the remaining bytes are the final record.
+    /// The record is released on the next read.
+    pub async fn next(&mut self) -> Result<Option<&[u8]>, Error<V::Error>> {
+        self.view.release(self.consumed);
+        self.consumed = 0;
+
+        let mut searched = 0;
+        loop {
+            let view = self.view.view();
+            if let Some(index) = view[searched..]
+                .iter()
+                .position(|byte| *byte == self.delimiter)
+            {
+                let len = searched + index;
+                if len > self.max_len {
+                    return Err(Error::TooLong(len as u64));
+                }
+                self.consumed = len + 1;
+                return Ok(Some(&self.view.view()[..len]));
+            }
+            searched = view.len();
+            if searched > self.max_len || searched >= self.max_grant {
+                return Err(Error::TooLong(searched as u64));
+            }
+
+            // Double the grant each time, to limit searching the same bytes.
+            let request = searched
+                .saturating_mul(2)
+                .max(64)
+                .min(self.max_len.saturating_add(1))
+                .min(self.max_grant);
+            if !grant_whole(&mut self.view, request, &mut self.max_grant).await? {
+                // Search again with the grant limited to the stream's maximum grant
+                continue;
+            }
+            if self.view.view().len() == searched {
+                // The stream ended
+                return if searched == 0 {
+                    Ok(None)
+                } else {
+                    self.consumed = searched;
+                    Ok(Some(self.view.view()))
+                };
+            }
+        }
+    }
+
+    /// Read the next record, blocking the current thread.
+    pub fn blocking_next(&mut self) -> Result<Option<&[u8]>, Error<V::Error>> {
+        futures::executor::block_on(self.next())
+    }
+}
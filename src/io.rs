@@ -1,4 +1,4 @@
-#![cfg(all(feature = "std"))]
+#![cfg(feature = "std")]
 #![cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
 //! Utilities for working with [`std::io`].
 
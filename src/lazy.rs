@@ -2,6 +2,7 @@
 
 use crate::{View, ViewMut};
 use core::{
+    future::Future,
     pin::Pin,
     sync::atomic::AtomicBool,
     task::{Context, Poll},
@@ -74,7 +75,7 @@ where
         }
     }
 
-    fn try_grant(self: &mut Self, count: usize) -> Result<bool, Self::Error> {
+    fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
         if count > 0 {
             if self.view.is_none() {
                 self.view.get_or_insert(self.init.take().unwrap()());
@@ -109,20 +110,168 @@ where
     }
 }
 
+/// A lazy-initialized view with an asynchronous, fallible initializer.
+///
+/// The initializer is only polled when the view is polled for a grant.
+/// If the initializer fails, the error is returned by the grant and the view is closed.
+///
+/// The initializer must be `Unpin`.
+/// Other futures can be used by pinning them first, such as with `Box::pin`.
+#[pin_project]
+#[derive(Copy, Clone, Debug, Hash)]
+pub struct LazyAsync<V, F> {
+    view: Option<V>,
+    init: Option<F>,
+}
+
+impl<V, F> LazyAsync<V, F> {
+    /// Create a new lazy view.
+    pub fn new(init: F) -> Self {
+        Self {
+            view: None,
+            init: Some(init),
+        }
+    }
+
+    /// Return the inner type, if it has been initialized.
+    pub fn into_inner(self) -> Option<V> {
+        self.view
+    }
+}
+
+impl<V, F, E> LazyAsync<V, F>
+where
+    V: View,
+    F: Future<Output = Result<V, E>> + Unpin,
+{
+    /// Poll the initializer, returning true if the view is initialized.
+    fn poll_init(
+        view: &mut Option<V>,
+        init: &mut Option<F>,
+        cx: &mut Context,
+    ) -> Poll<Result<bool, E>> {
+        if view.is_some() {
+            return Poll::Ready(Ok(true));
+        }
+        if let Some(f) = init.as_mut() {
+            let result = futures::ready!(Pin::new(f).poll(cx));
+            *init = None;
+            *view = Some(result?);
+            Poll::Ready(Ok(true))
+        } else {
+            // Initialization previously failed
+            Poll::Ready(Ok(false))
+        }
+    }
+}
+
+impl<V, F, E> View for LazyAsync<V, F>
+where
+    V: View,
+    F: Future<Output = Result<V, E>> + Unpin,
+    V::Error: Into<E>,
+    E: core::fmt::Debug,
+{
+    type Item = V::Item;
+    type Error = E;
+
+    fn view(&self) -> &[Self::Item] {
+        if let Some(view) = self.view.as_ref() {
+            view.view()
+        } else {
+            &[]
+        }
+    }
+
+    fn poll_grant(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        count: usize,
+    ) -> Poll<Result<(), Self::Error>> {
+        if count > 0 {
+            let this = self.project();
+            if futures::ready!(Self::poll_init(this.view, this.init, cx))? {
+                Pin::new(this.view.as_mut().unwrap())
+                    .poll_grant(cx, count)
+                    .map_err(Into::into)
+            } else {
+                Poll::Ready(Ok(()))
+            }
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
+        if count > 0 {
+            let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+            match Self::poll_init(&mut self.view, &mut self.init, &mut cx) {
+                Poll::Ready(Ok(true)) => self
+                    .view
+                    .as_mut()
+                    .unwrap()
+                    .try_grant(count)
+                    .map_err(Into::into),
+                Poll::Ready(Ok(false)) => Ok(true),
+                Poll::Ready(Err(e)) => Err(e),
+                Poll::Pending => Ok(false),
+            }
+        } else {
+            Ok(true)
+        }
+    }
+
+    fn release(&mut self, count: usize) {
+        if count > 0 {
+            self.view
+                .as_mut()
+                .expect("attempted to release greater than grant")
+                .release(count)
+        }
+    }
+}
+
+impl<V, F, E> ViewMut for LazyAsync<V, F>
+where
+    V: ViewMut,
+    F: Future<Output = Result<V, E>> + Unpin,
+    V::Error: Into<E>,
+    E: core::fmt::Debug,
+{
+    fn view_mut(&mut self) -> &mut [Self::Item] {
+        if let Some(view) = self.view.as_mut() {
+            view.view_mut()
+        } else {
+            &mut []
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
 mod channel {
     use super::*;
     use core::marker::PhantomData;
+    use futures::task::AtomicWaker;
+    use pin_project::pinned_drop;
     use std::sync::{atomic::Ordering, Arc, Mutex};
 
     struct LazyChannelImpl<Sink, Source, F> {
         ready: AtomicBool,
+        closed: AtomicBool, // true if the sink was dropped without initializing
         source: Mutex<Option<Source>>,
         init: Mutex<Option<F>>,
+        waker: AtomicWaker, // waker waited on by the source
         _sink: PhantomData<Sink>,
     }
 
+    impl<Sink, Source, F> LazyChannelImpl<Sink, Source, F> {
+        fn close(&self) {
+            self.closed.store(true, Ordering::Release);
+            self.waker.wake();
+        }
+    }
+
     impl<Sink, Source, F> LazyChannelImpl<Sink, Source, F>
     where
         F: FnOnce() -> (Sink, Source),
@@ -130,8 +279,10 @@ mod channel {
         fn new(f: F) -> Self {
             Self {
                 ready: AtomicBool::new(false),
+                closed: AtomicBool::new(false),
                 source: Mutex::new(None),
                 init: Mutex::new(Some(f)),
+                waker: AtomicWaker::new(),
                 _sink: PhantomData,
             }
         }
@@ -141,6 +292,7 @@ mod channel {
             let (sink, source) = init();
             self.source.lock().unwrap().replace(source);
             self.ready.store(true, Ordering::Release);
+            self.waker.wake();
             sink
         }
 
@@ -151,16 +303,44 @@ mod channel {
                 None
             }
         }
+
+        /// Take the source, if the sink has initialized the channel.
+        ///
+        /// Returns `None` if the sink was dropped without initializing the channel.
+        fn poll_take_source(&self, cx: &mut Context) -> Poll<Option<Source>> {
+            // Perform double-checking on initialization, as in `circular_buffer`.
+            if let Some(source) = self.try_take_source() {
+                return Poll::Ready(Some(source));
+            }
+            self.waker.register(cx.waker());
+            if let Some(source) = self.try_take_source() {
+                Poll::Ready(Some(source))
+            } else if self.closed.load(Ordering::Acquire) {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
+            }
+        }
     }
 
     /// A sink created by [`lazy_channel`].
-    #[pin_project]
+    #[pin_project(PinnedDrop)]
     #[cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
     pub struct LazyChannelSink<Sink, Source, F> {
         view: Option<Sink>,
         shared: Arc<LazyChannelImpl<Sink, Source, F>>,
     }
 
+    #[pinned_drop]
+    impl<Sink, Source, F> PinnedDrop for LazyChannelSink<Sink, Source, F> {
+        fn drop(self: Pin<&mut Self>) {
+            // If the channel was never initialized, the source would otherwise wait forever.
+            if self.view.is_none() {
+                self.shared.close();
+            }
+        }
+    }
+
     impl<Sink, Source, F> View for LazyChannelSink<Sink, Source, F>
     where
         Sink: crate::View,
@@ -193,8 +373,7 @@ mod channel {
             }
         }
 
-
-        fn try_grant(self: &mut Self, count: usize) -> Result<bool, Self::Error> {
+        fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
             if count > 0 {
                 if self.view.is_none() {
                     self.view.get_or_insert(self.shared.take_sink());
@@ -261,8 +440,13 @@ mod channel {
             if count > 0 {
                 let this = self.project();
                 if this.view.is_none() {
-                    if let Some(source) = this.shared.try_take_source() {
-                        this.view.get_or_insert(source);
+                    match this.shared.poll_take_source(cx) {
+                        Poll::Ready(Some(source)) => {
+                            this.view.get_or_insert(source);
+                        }
+                        // The sink closed without writing, so the stream is empty.
+                        Poll::Ready(None) => return Poll::Ready(Ok(())),
+                        Poll::Pending => return Poll::Pending,
                     }
                 }
                 Pin::new(this.view.as_mut().unwrap()).poll_grant(cx, count)
@@ -271,11 +455,14 @@ mod channel {
             }
         }
 
-        fn try_grant(self: &mut Self, count: usize) -> Result<bool, Self::Error> {
+        fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
             if count > 0 {
                 if self.view.is_none() {
                     if let Some(source) = self.shared.try_take_source() {
                         self.view.get_or_insert(source);
+                    } else {
+                        // The stream is empty if the sink closed without writing.
+                        return Ok(self.shared.closed.load(Ordering::Acquire));
                     }
                 }
                 self.view.as_mut().unwrap().try_grant(count)
@@ -311,6 +498,9 @@ mod channel {
     /// Create a lazy-initialized channel.
     ///
     /// The channel is only initialized when first writing to the sink.
+    /// Until then, the source waits for the channel to be initialized.
+    /// If the sink is dropped without initializing the channel, the source reaches the end of the
+    /// stream.
     #[cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
     pub fn lazy_channel<Sink, Source, F>(
         f: F,
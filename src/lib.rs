@@ -61,14 +61,24 @@
 //! }
 //! ```
 
+pub mod cast;
 pub mod circular_buffer;
+pub mod driver;
+pub mod endian;
 pub mod error;
+pub mod file;
+pub mod framing;
 pub mod io;
 pub mod lazy;
+pub mod pipeline;
+pub mod process;
 pub mod slice;
 pub mod splittable;
 pub mod view;
 
+mod sync;
+
 pub use circular_buffer::circular_buffer;
+pub use error::Error;
 pub use splittable::SplittableView;
 pub use view::{View, ViewMut};
@@ -0,0 +1,359 @@
+#![cfg(feature = "std")]
+#![cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
+//! Pipelines of connected stages.
+//!
+//! A [`Pipeline`] is a set of stages, connected by [`circular_buffer`]s.
+//! Each stage is a future that reads from and writes to its connections.
+//!
+//! Pipelines can be run in a single task with [`Pipeline::run`], each stage can be spawned
+//! on an executor with [`Pipeline::spawn`], or the pipeline can be run without an executor with
+//! [`Pipeline::blocking_run`].
+//! In either case, the pipeline completes when every stage completes.
+//! If any stage fails, the remaining stages are cancelled and the failed stage is reported.
+//! Spawned stages that panic, or are dropped by the executor, are reported as failed rather than
+//! panicking the pipeline.
+//!
+//! # Example
+//! ```
+//! use rivulet::{pipeline::Pipeline, process, SplittableView, View, ViewMut};
+//!
+//! let mut pipeline = Pipeline::<rivulet::Error>::new();
+//!
+//! let numbers = pipeline.source("generate", 4096, |mut sink| async move {
+//!     for i in 0..100u32 {
+//!         sink.grant(1).await?;
+//!         sink.view_mut()[0] = i;
+//!         sink.release(1);
+//!     }
+//!     Ok::<_, rivulet::Error>(())
+//! });
+//!
+//! let doubled = pipeline.transform("double", numbers, 4096, |source, sink| {
+//!     process::copy_with(source.into_view(), sink, |input: &[u32], output: &mut [u32]| {
+//!         for (x, y) in input.iter().zip(output) {
+//!             *y = x * 2;
+//!         }
+//!     })
+//! });
+//!
+//! pipeline.sink("check", doubled, |source| async move {
+//!     let mut source = source.into_view();
+//!     let mut expected = 0;
+//!     loop {
+//!         source.grant(1).await?;
+//!         let view = source.view();
+//!         if view.is_empty() {
+//!             break;
+//!         }
+//!         for x in view {
+//!             assert_eq!(*x, expected * 2);
+//!             expected += 1;
+//!         }
+//!         let len = view.len();
+//!         source.release(len);
+//!     }
+//!     Ok::<_, rivulet::Error>(())
+//! });
+//!
+//! futures::executor::block_on(pipeline.run()).unwrap();
+//! ```
+
+use crate::{
+    circular_buffer::{circular_buffer, Sink, Source},
+    driver::Driver,
+};
+use futures::{
+    channel::oneshot,
+    future::{AbortHandle, Abortable, FutureExt},
+    stream::{FuturesUnordered, StreamExt},
+};
+use std::{future::Future, panic::AssertUnwindSafe, pin::Pin};
+
+type StageFuture<E> = Pin<Box<dyn Future<Output = Result<(), E>> + Send>>;
+
+/// The reason a stage of a pipeline failed.
+#[derive(Debug)]
+pub enum Failure<E> {
+    /// The stage returned an error.
+    Error(E),
+
+    /// The stage panicked.
+    Panicked,
+
+    /// The stage's task was dropped by the executor before it completed.
+    Cancelled,
+}
+
+/// An error produced by a stage of a pipeline.
+#[derive(Debug)]
+pub struct Error<E> {
+    stage: String,
+    failure: Failure<E>,
+}
+
+impl<E> Error<E> {
+    /// The name of the stage that failed.
+    pub fn stage(&self) -> &str {
+        &self.stage
+    }
+
+    /// The reason the stage failed.
+    pub fn failure(&self) -> &Failure<E> {
+        &self.failure
+    }
+
+    /// The error produced by the stage, if it returned one.
+    pub fn error(&self) -> Option<&E> {
+        match &self.failure {
+            Failure::Error(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Return the reason the stage failed.
+    pub fn into_inner(self) -> Failure<E> {
+        self.failure
+    }
+}
+
+impl<E> core::fmt::Display for Error<E>
+where
+    E: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        match &self.failure {
+            Failure::Error(e) => write!(f, "stage `{}` failed: {}", self.stage, e),
+            Failure::Panicked => write!(f, "stage `{}` panicked", self.stage),
+            Failure::Cancelled => write!(f, "stage `{}` did not complete", self.stage),
+        }
+    }
+}
+
+impl<E> std::error::Error for Error<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.failure {
+            Failure::Error(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A connection from one stage to the next, produced by [`Pipeline::source`] and
+/// [`Pipeline::transform`].
+pub struct Connection<T>(Source<T>);
+
+impl<T> Connection<T> {
+    /// Return the source of the connection, for reading it outside of the pipeline.
+    pub fn into_inner(self) -> Source<T> {
+        self.0
+    }
+}
+
+/// A set of stages, connected by circular buffers.
+///
+/// Stage errors are converted to the error type `E`.
+pub struct Pipeline<E = crate::Error> {
+    stages: Vec<(String, StageFuture<E>)>,
+}
+
+impl<E> Default for Pipeline<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> Pipeline<E> {
+    /// Create a new, empty pipeline.
+    pub fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    /// Return the number of stages in the pipeline.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Return true if the pipeline has no stages.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Create a circular buffer for connecting stages, with a capacity of at least `min_size`.
+    ///
+    /// This can be used with [`stage`](`Self::stage`) for pipelines that aren't a simple chain,
+    /// such as stages with multiple inputs or outputs.
+    pub fn channel<T>(&mut self, min_size: usize) -> (Sink<T>, Source<T>)
+    where
+        T: Send + Sync + Default + 'static,
+    {
+        circular_buffer(min_size)
+    }
+
+    /// Add a stage to the pipeline.
+    pub fn stage<F, T, SE>(&mut self, name: impl Into<String>, stage: F)
+    where
+        F: Future<Output = Result<T, SE>> + Send + 'static,
+        SE: Into<E>,
+    {
+        let stage = async move {
+            stage.await.map_err(Into::into)?;
+            Ok(())
+        };
+        self.stages.push((name.into(), Box::pin(stage)));
+    }
+
+    /// Add a stage that writes to a new connection, with a capacity of at least `min_size`.
+    pub fn source<T, F, Fut, O, SE>(
+        &mut self,
+        name: impl Into<String>,
+        min_size: usize,
+        stage: F,
+    ) -> Connection<T>
+    where
+        T: Send + Sync + Default + 'static,
+        F: FnOnce(Sink<T>) -> Fut,
+        Fut: Future<Output = Result<O, SE>> + Send + 'static,
+        SE: Into<E>,
+    {
+        let (sink, source) = self.channel(min_size);
+        self.stage(name, stage(sink));
+        Connection(source)
+    }
+
+    /// Add a stage that reads from `input` and writes to a new connection, with a capacity of at
+    /// least `min_size`.
+    pub fn transform<T, U, F, Fut, O, SE>(
+        &mut self,
+        name: impl Into<String>,
+        input: Connection<T>,
+        min_size: usize,
+        stage: F,
+    ) -> Connection<U>
+    where
+        U: Send + Sync + Default + 'static,
+        F: FnOnce(Source<T>, Sink<U>) -> Fut,
+        Fut: Future<Output = Result<O, SE>> + Send + 'static,
+        SE: Into<E>,
+    {
+        let (sink, source) = self.channel(min_size);
+        self.stage(name, stage(input.0, sink));
+        Connection(source)
+    }
+
+    /// Add a stage that reads from `input`.
+    pub fn sink<T, F, Fut, O, SE>(
+        &mut self,
+        name: impl Into<String>,
+        input: Connection<T>,
+        stage: F,
+    ) where
+        F: FnOnce(Source<T>) -> Fut,
+        Fut: Future<Output = Result<O, SE>> + Send + 'static,
+        SE: Into<E>,
+    {
+        self.stage(name, stage(input.0));
+    }
+
+    /// Run every stage of the pipeline in the current task.
+    ///
+    /// If a stage fails, the remaining stages are dropped, closing their connections.
+    /// Dropping the returned future cancels the pipeline.
+    pub async fn run(self) -> Result<(), Error<E>> {
+        let mut stages = self
+            .stages
+            .into_iter()
+            .map(|(name, stage)| async move { (name, stage.await) })
+            .collect::<FuturesUnordered<_>>();
+        while let Some((stage, result)) = stages.next().await {
+            if let Err(error) = result {
+                return Err(Error {
+                    stage,
+                    failure: Failure::Error(error),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Run every stage of the pipeline on the current thread, without an async runtime.
+    ///
+    /// Stages are polled by a [`Driver`], and only polled when woken.
+    /// If a stage fails, the remaining stages are dropped, closing their connections.
+    pub fn blocking_run(self) -> Result<(), Error<E>> {
+        let mut driver = Driver::new();
+        for (name, stage) in self.stages {
+            driver.push(async move { (name, stage.await) });
+        }
+        for (_, (stage, result)) in driver {
+            if let Err(error) = result {
+                return Err(Error {
+                    stage,
+                    failure: Failure::Error(error),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<E> Pipeline<E>
+where
+    E: Send + 'static,
+{
+    /// Spawn every stage of the pipeline as a separate task, using the function `spawn`.
+    ///
+    /// The returned future completes when every stage completes.
+    /// If a stage fails, panics, or is dropped by the executor, the remaining stages are aborted,
+    /// closing their connections.
+    /// Dropping the returned future aborts every stage.
+    pub async fn spawn<S>(self, mut spawn: S) -> Result<(), Error<E>>
+    where
+        S: FnMut(Pin<Box<dyn Future<Output = ()> + Send>>),
+    {
+        /// Aborts the stages when the pipeline completes or is cancelled.
+        struct AbortOnDrop(Vec<AbortHandle>);
+
+        impl Drop for AbortOnDrop {
+            fn drop(&mut self) {
+                for handle in &self.0 {
+                    handle.abort();
+                }
+            }
+        }
+
+        let mut handles = AbortOnDrop(Vec::with_capacity(self.stages.len()));
+        let mut stages = FuturesUnordered::new();
+        for (name, stage) in self.stages {
+            let (handle, registration) = AbortHandle::new_pair();
+            let (sender, receiver) = oneshot::channel();
+            let stage = async move {
+                match AssertUnwindSafe(stage).catch_unwind().await {
+                    Ok(Ok(())) => None,
+                    Ok(Err(error)) => Some(Failure::Error(error)),
+                    Err(_) => Some(Failure::Panicked),
+                }
+            };
+            spawn(Box::pin(async move {
+                if let Ok(failure) = Abortable::new(stage, registration).await {
+                    let _ = sender.send(failure);
+                }
+            }));
+            handles.0.push(handle);
+            stages.push(async move { (name, receiver.await) });
+        }
+
+        while let Some((stage, result)) = stages.next().await {
+            let failure = match result {
+                Ok(None) => continue,
+                Ok(Some(failure)) => failure,
+                // The stage's task was dropped without completing
+                Err(oneshot::Canceled) => Failure::Cancelled,
+            };
+            return Err(Error { stage, failure });
+        }
+        Ok(())
+    }
+}
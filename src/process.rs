@@ -0,0 +1,239 @@
+//! Moving data between streams.
+//!
+//! Many pipeline stages only read from one stream and write to another.
+//! The functions in this module implement the loop of granting, processing, and releasing both
+//! streams.
+
+use crate::{View, ViewMut};
+use futures::future::TryFutureExt;
+
+/// An error produced while moving data between two streams.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Error<R, W> {
+    /// The source produced an error.
+    Source(R),
+
+    /// The sink produced an error.
+    Sink(W),
+}
+
+impl<R, W> core::fmt::Display for Error<R, W>
+where
+    R: core::fmt::Display,
+    W: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::Source(e) => write!(f, "source error: {}", e),
+            Self::Sink(e) => write!(f, "sink error: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R, W> std::error::Error for Error<R, W>
+where
+    R: std::error::Error + 'static,
+    W: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Source(e) => Some(e),
+            Self::Sink(e) => Some(e),
+        }
+    }
+}
+
+impl<R, W> From<Error<R, W>> for crate::Error
+where
+    R: Into<crate::Error>,
+    W: Into<crate::Error>,
+{
+    fn from(e: Error<R, W>) -> Self {
+        match e {
+            Error::Source(e) => e.into(),
+            Error::Sink(e) => e.into(),
+        }
+    }
+}
+
+/// Obtain grants of at least `source_count` and `sink_count` elements, concurrently.
+async fn grant_both<R, W>(
+    source: &mut R,
+    sink: &mut W,
+    source_count: usize,
+    sink_count: usize,
+) -> Result<(), Error<R::Error, W::Error>>
+where
+    R: View,
+    W: ViewMut,
+{
+    // Either error ends the stream, so don't wait on the other grant.
+    futures::future::try_join(
+        source.grant(source_count).map_err(Error::Source),
+        sink.grant(sink_count).map_err(Error::Sink),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Copy all elements from `source` to `sink`.
+///
+/// Copying stops when either stream ends.
+/// Returns the number of elements copied.
+pub async fn copy<R, W>(source: R, sink: W) -> Result<u64, Error<R::Error, W::Error>>
+where
+    R: View,
+    W: ViewMut<Item = R::Item>,
+    R::Item: Clone,
+{
+    copy_with(source, sink, |input, output| output.clone_from_slice(input)).await
+}
+
+/// Transform all elements from `source` into `sink`, one element for each element.
+///
+/// Each call to `f` receives as many elements as are available in both streams, and must fill
+/// the entire output slice, which is the same length as the input slice.
+///
+/// Copying stops when either stream ends.
+/// Returns the number of elements copied.
+pub async fn copy_with<R, W, F>(
+    mut source: R,
+    mut sink: W,
+    mut f: F,
+) -> Result<u64, Error<R::Error, W::Error>>
+where
+    R: View,
+    W: ViewMut,
+    F: FnMut(&[R::Item], &mut [W::Item]),
+{
+    let mut copied = 0;
+    loop {
+        grant_both(&mut source, &mut sink, 1, 1).await?;
+
+        let input = source.view();
+        let output = sink.view_mut();
+        let count = input.len().min(output.len());
+        if count == 0 {
+            break Ok(copied);
+        }
+
+        f(&input[..count], &mut output[..count]);
+        source.release(count);
+        sink.release(count);
+        copied += count as u64;
+    }
+}
+
+/// Copy all elements from `source` to `sink`, blocking the current thread.
+///
+/// See [`copy`].
+pub fn blocking_copy<R, W>(source: R, sink: W) -> Result<u64, Error<R::Error, W::Error>>
+where
+    R: View,
+    W: ViewMut<Item = R::Item>,
+    R::Item: Clone,
+{
+    futures::executor::block_on(copy(source, sink))
+}
+
+/// Transform all elements from `source` into `sink`, blocking the current thread.
+///
+/// See [`copy_with`].
+pub fn blocking_copy_with<R, W, F>(
+    source: R,
+    sink: W,
+    f: F,
+) -> Result<u64, Error<R::Error, W::Error>>
+where
+    R: View,
+    W: ViewMut,
+    F: FnMut(&[R::Item], &mut [W::Item]),
+{
+    futures::executor::block_on(copy_with(source, sink, f))
+}
+
+/// Transform blocks of `m` elements from `source` into blocks of `n` elements in `sink`.
+///
+/// Each call to `f` receives one block of `m` input elements and must fill one block of `n`
+/// output elements.
+/// As many whole blocks as are available in both streams are processed per grant.
+///
+/// When the source ends with fewer than `m` elements remaining, `tail` is called with the
+/// remaining input and the available output, and returns the number of output elements it
+/// filled.
+///
+/// Processing stops when either stream ends.
+/// Returns the number of whole blocks processed.
+///
+/// # Panics
+/// Panics if `m` or `n` is zero, or if `tail` fills more elements than available.
+pub async fn process<R, W, F, T>(
+    mut source: R,
+    mut sink: W,
+    m: usize,
+    n: usize,
+    mut f: F,
+    tail: T,
+) -> Result<u64, Error<R::Error, W::Error>>
+where
+    R: View,
+    W: ViewMut,
+    F: FnMut(&[R::Item], &mut [W::Item]),
+    T: FnOnce(&[R::Item], &mut [W::Item]) -> usize,
+{
+    assert!(m > 0 && n > 0, "block sizes must be nonzero");
+
+    let mut processed = 0;
+    loop {
+        grant_both(&mut source, &mut sink, m, n).await?;
+
+        let input = source.view();
+        let output = sink.view_mut();
+        let blocks = (input.len() / m).min(output.len() / n);
+        if blocks == 0 {
+            if input.len() < m && !input.is_empty() {
+                let len = input.len();
+                let filled = tail(input, output);
+                assert!(
+                    filled <= output.len(),
+                    "tail filled more than the available output"
+                );
+                source.release(len);
+                sink.release(filled);
+            }
+            break Ok(processed);
+        }
+
+        for (input, output) in input
+            .chunks_exact(m)
+            .zip(output.chunks_exact_mut(n))
+            .take(blocks)
+        {
+            f(input, output);
+        }
+        source.release(blocks * m);
+        sink.release(blocks * n);
+        processed += blocks as u64;
+    }
+}
+
+/// Transform blocks of elements from `source` into `sink`, blocking the current thread.
+///
+/// See [`process`].
+pub fn blocking_process<R, W, F, T>(
+    source: R,
+    sink: W,
+    m: usize,
+    n: usize,
+    f: F,
+    tail: T,
+) -> Result<u64, Error<R::Error, W::Error>>
+where
+    R: View,
+    W: ViewMut,
+    F: FnMut(&[R::Item], &mut [W::Item]),
+    T: FnOnce(&[R::Item], &mut [W::Item]) -> usize,
+{
+    futures::executor::block_on(process(source, sink, m, n, f, tail))
+}
@@ -5,6 +5,7 @@ use core::{
     convert::Infallible,
     convert::TryInto,
     marker::PhantomData,
+    ops::Deref,
     pin::Pin,
     slice,
     task::{Context, Poll, Waker},
@@ -47,7 +48,7 @@ unsafe impl<'a, T> SplittableViewImpl for Slice<'a, T> {
         Poll::Ready(Ok(len - index.min(len)))
     }
 
-    fn try_available(self: &Self, index: u64, _len: usize) -> Result<usize, Self::Error> {
+    fn try_available(&self, index: u64, _len: usize) -> Result<usize, Self::Error> {
         let index: usize = index.try_into().unwrap();
         let len = self.0.as_ref().len();
         Ok(len - index.min(len))
@@ -106,7 +107,7 @@ unsafe impl<'a, T> SplittableViewImpl for SliceMut<'a, T> {
         Poll::Ready(Ok(self.len - index.min(self.len)))
     }
 
-    fn try_available(self: &Self, index: u64, _len: usize) -> Result<usize, Self::Error> {
+    fn try_available(&self, index: u64, _len: usize) -> Result<usize, Self::Error> {
         let index: usize = index.try_into().unwrap();
         Ok(self.len - index.min(self.len))
     }
@@ -121,3 +122,246 @@ unsafe impl<'a, T> SplittableViewImplMut for SliceMut<'a, T> {
         slice::from_raw_parts_mut(self.ptr.add(index.try_into().unwrap()), len)
     }
 }
+
+/// Treats an owned slice, such as a `Vec<T>`, `Box<[T]>` or `Arc<[T]>`, as a stream.
+///
+/// Unlike [`Slice`], this doesn't borrow its storage, so it can be moved into other tasks or
+/// threads.
+pub struct Owned<S>(S);
+
+impl<S> Owned<S> {
+    /// Create a stream from an owned slice.
+    pub fn new(storage: S) -> Self {
+        Self(storage)
+    }
+
+    /// Return the original storage.
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+unsafe impl<T, S> SplittableViewImpl for Owned<S>
+where
+    S: Deref<Target = [T]> + Unpin,
+{
+    type Item = T;
+    type Error = Infallible;
+
+    unsafe fn set_reader_waker(&self, _: impl Fn() + Send + Sync + 'static) {}
+
+    unsafe fn set_head(&self, _: u64) {}
+
+    unsafe fn compare_set_head(&self, _: u64) {}
+
+    fn poll_available(
+        self: Pin<&Self>,
+        _cx: &mut Context,
+        _register_wakeup: impl FnOnce(&Waker),
+        index: u64,
+        len: usize,
+    ) -> Poll<Result<usize, Self::Error>> {
+        Poll::Ready(self.try_available(index, len))
+    }
+
+    fn try_available(&self, index: u64, _len: usize) -> Result<usize, Self::Error> {
+        let index: usize = index.try_into().unwrap();
+        let len = self.0.len();
+        Ok(len - index.min(len))
+    }
+
+    unsafe fn view(&self, index: u64, len: usize) -> &[Self::Item] {
+        let index = index.try_into().unwrap();
+        &self.0[index..index + len]
+    }
+}
+
+/// Treats an owned mutable slice, such as a `Vec<T>` or `Box<[T]>`, as a stream.
+///
+/// Unlike [`SliceMut`], this doesn't borrow its storage, so it can be moved into other tasks or
+/// threads.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
+pub struct OwnedMut<T>(Box<[core::cell::UnsafeCell<T>]>);
+
+// Safety: mutable views never overlap, so sharing this is no different than sharing `[T]`.
+#[cfg(feature = "std")]
+unsafe impl<T> Sync for OwnedMut<T> where T: Send + Sync {}
+
+#[cfg(feature = "std")]
+impl<T> OwnedMut<T> {
+    /// Create a stream from an owned slice.
+    pub fn new(storage: impl Into<Box<[T]>>) -> Self {
+        // Safety: `UnsafeCell<T>` has the same layout as `T`
+        let storage = unsafe {
+            Box::from_raw(Box::into_raw(storage.into()) as *mut [core::cell::UnsafeCell<T>])
+        };
+        Self(storage)
+    }
+
+    /// Return the original storage.
+    pub fn into_inner(self) -> Box<[T]> {
+        // Safety: `UnsafeCell<T>` has the same layout as `T`
+        unsafe { Box::from_raw(Box::into_raw(self.0) as *mut [T]) }
+    }
+}
+
+#[cfg(feature = "std")]
+unsafe impl<T> SplittableViewImpl for OwnedMut<T> {
+    type Item = T;
+    type Error = Infallible;
+
+    unsafe fn set_reader_waker(&self, _: impl Fn() + Send + Sync + 'static) {}
+
+    unsafe fn set_head(&self, _: u64) {}
+
+    unsafe fn compare_set_head(&self, _: u64) {}
+
+    fn poll_available(
+        self: Pin<&Self>,
+        _cx: &mut Context,
+        _register_wakeup: impl FnOnce(&Waker),
+        index: u64,
+        len: usize,
+    ) -> Poll<Result<usize, Self::Error>> {
+        Poll::Ready(self.try_available(index, len))
+    }
+
+    fn try_available(&self, index: u64, _len: usize) -> Result<usize, Self::Error> {
+        let index: usize = index.try_into().unwrap();
+        let len = self.0.len();
+        Ok(len - index.min(len))
+    }
+
+    unsafe fn view(&self, index: u64, len: usize) -> &[Self::Item] {
+        let index: usize = index.try_into().unwrap();
+        slice::from_raw_parts(self.0[index..index + len].as_ptr() as *const T, len)
+    }
+}
+
+#[cfg(feature = "std")]
+unsafe impl<T> SplittableViewImplMut for OwnedMut<T> {
+    unsafe fn view_mut(&self, index: u64, len: usize) -> &mut [Self::Item] {
+        let index: usize = index.try_into().unwrap();
+        slice::from_raw_parts_mut(self.0[index..index + len].as_ptr() as *mut T, len)
+    }
+}
+
+/// A sink that collects written data into a growable `Vec<T>`.
+///
+/// The vector grows as large as necessary to satisfy each grant, unless a maximum size is
+/// provided.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
+#[derive(Clone, Debug, Default)]
+pub struct VecSink<T> {
+    vec: Vec<T>,
+    len: usize,
+    max_len: Option<usize>,
+}
+
+#[cfg(feature = "std")]
+impl<T> VecSink<T> {
+    /// Create an empty sink.
+    pub fn new() -> Self {
+        Self {
+            vec: Vec::new(),
+            len: 0,
+            max_len: None,
+        }
+    }
+
+    /// Create an empty sink that collects no more than `max_len` elements.
+    ///
+    /// Grants exceeding the remaining space produce a [`GrantOverflow`](crate::error::GrantOverflow) error.
+    pub fn with_max_len(max_len: usize) -> Self {
+        Self {
+            vec: Vec::new(),
+            len: 0,
+            max_len: Some(max_len),
+        }
+    }
+
+    /// Return the released data.
+    pub fn into_inner(mut self) -> Vec<T> {
+        self.vec.truncate(self.len);
+        self.vec
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> crate::View for VecSink<T>
+where
+    T: Default + Unpin,
+{
+    type Item = T;
+    type Error = crate::error::GrantOverflow;
+
+    fn view(&self) -> &[T] {
+        &self.vec[self.len..]
+    }
+
+    fn poll_grant(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context,
+        count: usize,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(self.try_grant(count).map(|_| ()))
+    }
+
+    fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
+        if let Some(max_len) = self.max_len {
+            let remaining = max_len - self.len;
+            if count > remaining {
+                return Err(crate::error::GrantOverflow(remaining));
+            }
+        }
+
+        let len = self.len + count;
+        if self.vec.len() < len {
+            self.vec.resize_with(len, T::default);
+        }
+        Ok(true)
+    }
+
+    fn release(&mut self, count: usize) {
+        assert!(
+            count <= self.vec.len() - self.len,
+            "attempted to release more than current grant"
+        );
+        self.len += count;
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> crate::ViewMut for VecSink<T>
+where
+    T: Default + Unpin,
+{
+    fn view_mut(&mut self) -> &mut [T] {
+        &mut self.vec[self.len..]
+    }
+}
+
+/// Read a view to the end of the stream, collecting the data into a `Vec<T>`.
+///
+/// To collect a view without an asynchronous runtime, use [`futures::executor::block_on`].
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
+pub async fn collect<V>(mut source: V) -> Result<Vec<V::Item>, V::Error>
+where
+    V: crate::View,
+    V::Item: Clone,
+{
+    let mut vec = Vec::new();
+    loop {
+        source.grant(1).await?;
+        let view = source.view();
+        if view.is_empty() {
+            break Ok(vec);
+        }
+        vec.extend_from_slice(view);
+        let len = view.len();
+        source.release(len);
+    }
+}
@@ -21,6 +21,10 @@ pub use sequence::{First, Second};
 ///
 /// # Safety
 /// The implementation must satisfy the various interior mutability conditions specified in each method.
+///
+/// Accesses to the data must be synchronized between threads: heads are published with release
+/// semantics so data is read before it's overwritten, and available data is published with
+/// release semantics after it's written.
 pub unsafe trait SplittableViewImpl: Sized + Unpin {
     /// The streamed type.
     type Item;
@@ -36,6 +40,25 @@ pub unsafe trait SplittableViewImpl: Sized + Unpin {
     /// Only set the waker if you have unique ownership of this.
     unsafe fn set_reader_waker(&self, waker: impl Fn() + Send + Sync + 'static);
 
+    /// Set the reader waking function, which is passed the end of the available data.
+    ///
+    /// The end is the index following the last available element, or `None` if it's unknown,
+    /// such as when the stream is closed.
+    /// Readers may use it to avoid waking before enough data is available.
+    ///
+    /// This should only be called once, after creation, instead of
+    /// [`set_reader_waker`](`Self::set_reader_waker`).
+    /// The default implementation always passes `None`.
+    ///
+    /// # Safety
+    /// See [`set_reader_waker`](`Self::set_reader_waker`).
+    unsafe fn set_reader_waker_with_end(
+        &self,
+        waker: impl Fn(Option<u64>) + Send + Sync + 'static,
+    ) {
+        self.set_reader_waker(move || waker(None))
+    }
+
     /// Set the earliest position retained in the stream.
     ///
     /// # Panics
@@ -68,11 +91,7 @@ pub unsafe trait SplittableViewImpl: Sized + Unpin {
     ) -> Poll<Result<usize, Self::Error>>;
 
     /// Test if a given amount is available
-    fn try_available(
-        self: &Self,
-        index: u64,
-        len: usize
-    ) -> Result<usize, Self::Error>;
+    fn try_available(&self, index: u64, len: usize) -> Result<usize, Self::Error>;
 
     /// Obtain a view into the stream.
     ///
@@ -93,7 +112,7 @@ pub unsafe trait SplittableViewImplMut: SplittableViewImpl {
     ///
     /// # Safety
     /// * The parameters must be within an available window as returned by
-    /// [`poll_available`](`SplittableViewImpl::poll_available`).
+    ///   [`poll_available`](`SplittableViewImpl::poll_available`).
     /// * The view must not overlap with any other view of this stream.
     ///
     /// Note that this function produces a mutable reference from a regular reference.
@@ -115,6 +134,18 @@ pub trait SplittableView: SplittableViewImpl {
         Cloneable::new(self)
     }
 
+    /// Create a view that implements `Clone`, with up to `max_readers` clones existing at once.
+    ///
+    /// Every reader is allocated up front, so the writer never locks or allocates when waking
+    /// the readers.
+    /// This is suitable for real-time threads, such as audio callbacks.
+    ///
+    /// # Panics
+    /// Panics if `max_readers` is zero.
+    fn into_cloneable_view_with_max_readers(self, max_readers: usize) -> Cloneable<Self> {
+        Cloneable::with_max_readers(self, max_readers)
+    }
+
     /// Split this view into two sequential views, such that data released by `First` becomes
     /// accessible to `Second`.
     fn sequence(self) -> (First<Self>, Second<Self>) {
@@ -123,7 +154,17 @@ pub trait SplittableView: SplittableViewImpl {
 }
 
 /// A mutable view that can be split for use with multiple readers.
-pub trait SplittableViewMut: SplittableView + SplittableViewImplMut {}
+pub trait SplittableViewMut: SplittableView + SplittableViewImplMut {
+    /// Create a mutable view for a single reader, along with a view that implements `Clone`.
+    ///
+    /// The mutable view has exclusive access to the stream and may modify data in place.
+    /// Data released by the mutable view becomes accessible to the cloneable view (and all of its
+    /// clones).
+    fn into_cloneable_view_mut(self) -> (View<First<Self>>, Cloneable<Second<Self>>) {
+        let (first, second) = self.sequence();
+        (first.into_view(), second.into_cloneable_view())
+    }
+}
 
 impl<T> SplittableView for T where T: SplittableViewImpl {}
 impl<T> SplittableViewMut for T where T: SplittableView + SplittableViewImplMut {}
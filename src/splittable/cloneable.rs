@@ -1,64 +1,194 @@
 use super::SplittableView;
-use futures::task::AtomicWaker;
+use crate::sync::{
+    atomic::{fence, AtomicBool, AtomicU64, Ordering},
+    Arc, AtomicWaker, RwLock,
+};
 use std::{
     convert::TryInto,
     pin::Pin,
-    sync::{
-        atomic::{AtomicU64, Ordering},
-        Arc, RwLock,
-    },
     task::{Context, Poll},
 };
 
 struct Reader {
     waker: AtomicWaker,
     head: AtomicU64,
+
+    /// The end of the data the reader is waiting for
+    wake_at: AtomicU64,
 }
 
-pub(crate) struct Waker(RwLock<Vec<Arc<Reader>>>);
+impl Reader {
+    fn new(head: u64) -> Self {
+        Self {
+            waker: AtomicWaker::new(),
+            head: AtomicU64::new(head),
+            wake_at: AtomicU64::new(0),
+        }
+    }
+
+    /// Register the waker, waking the reader once the data up to `wake_at` is available.
+    fn register(&self, waker: &core::task::Waker, wake_at: u64) {
+        self.wake_at.store(wake_at, Ordering::Relaxed);
+        self.waker.register(waker);
+
+        // The reader checks the available data after registering.
+        // Together with the fence in `Waker::wake`, either the reader observes the new data or
+        // the writer observes `wake_at`.
+        fence(Ordering::SeqCst);
+    }
+
+    /// Wake the reader if the data it's waiting for is available.
+    fn wake(&self, end: Option<u64>) {
+        match end {
+            Some(end) if end < self.wake_at.load(Ordering::Relaxed) => {}
+            _ => self.waker.wake(),
+        }
+    }
+}
+
+/// A preallocated reader, used when the number of readers is fixed.
+struct Slot {
+    used: AtomicBool,
+    reader: Arc<Reader>,
+}
+
+enum Readers {
+    /// Any number of readers, protected by a lock.
+    Dynamic(RwLock<Vec<Arc<Reader>>>),
+
+    /// A fixed number of readers, which never locks or allocates.
+    Fixed(Box<[Slot]>),
+}
+
+pub(crate) struct Waker {
+    readers: Readers,
+
+    /// Updated by every reader before finding the earliest head
+    epoch: AtomicU64,
+}
 
 impl Waker {
     /// Create a new waker with one reader
     pub(crate) fn new() -> Self {
-        let reader = Reader {
-            waker: AtomicWaker::new(),
-            head: AtomicU64::new(0),
-        };
-        Self(RwLock::new(vec![Arc::new(reader)]))
+        Self::with_readers(Readers::Dynamic(RwLock::new(vec![Arc::new(Reader::new(
+            0,
+        ))])))
     }
 
-    /// Wake all readers
-    pub(crate) fn wake(&self) {
-        let lock = self.0.read().expect("another thread panicked");
-        for reader in lock.iter() {
-            reader.waker.wake();
+    /// Create a new waker with one reader, and space for up to `max_readers` readers
+    pub(crate) fn with_max_readers(max_readers: usize) -> Self {
+        assert!(max_readers > 0, "at least one reader is required");
+        let slots = (0..max_readers)
+            .map(|i| Slot {
+                used: AtomicBool::new(i == 0),
+                reader: Arc::new(Reader::new(0)),
+            })
+            .collect();
+        Self::with_readers(Readers::Fixed(slots))
+    }
+
+    fn with_readers(readers: Readers) -> Self {
+        Self {
+            readers,
+            epoch: AtomicU64::new(0),
         }
     }
 
-    /// Copy the specified reader
-    fn insert(&self, reader: &Arc<Reader>) -> Arc<Reader> {
-        let reader = Arc::new(Reader {
-            waker: AtomicWaker::new(),
-            head: AtomicU64::new(reader.head.load(Ordering::Relaxed)),
-        });
-        let mut lock = self.0.write().expect("another thread panicked");
-        lock.push(reader.clone());
-        reader
+    /// Return the first reader
+    fn first(&self) -> Arc<Reader> {
+        match &self.readers {
+            Readers::Dynamic(readers) => {
+                readers.read().expect("another thread panicked")[0].clone()
+            }
+            Readers::Fixed(slots) => slots[0].reader.clone(),
+        }
+    }
+
+    /// Wake the readers waiting for data up to `end`, or all readers if `end` is unknown
+    pub(crate) fn wake(&self, end: Option<u64>) {
+        // See `Reader::register`
+        fence(Ordering::SeqCst);
+
+        match &self.readers {
+            Readers::Dynamic(readers) => {
+                let lock = readers.read().expect("another thread panicked");
+                for reader in lock.iter() {
+                    reader.wake(end);
+                }
+            }
+            Readers::Fixed(slots) => {
+                // Unused slots are woken too, since a reader may be inserted concurrently.
+                // Removed readers take their wakers, so this doesn't wake them.
+                for slot in slots.iter() {
+                    slot.reader.wake(end);
+                }
+            }
+        }
+    }
+
+    /// Copy the specified reader, returning `None` if there is no space for another reader
+    fn insert(&self, reader: &Arc<Reader>) -> Option<Arc<Reader>> {
+        let head = reader.head.load(Ordering::Relaxed);
+        match &self.readers {
+            Readers::Dynamic(readers) => {
+                let reader = Arc::new(Reader::new(head));
+                let mut lock = readers.write().expect("another thread panicked");
+                lock.push(reader.clone());
+                Some(reader)
+            }
+            Readers::Fixed(slots) => {
+                // The copied reader remains in use while inserting, so the earliest head can't
+                // pass `head` before the new reader's head is set.
+                let slot = slots.iter().find(|slot| {
+                    slot.used
+                        .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+                        .is_ok()
+                })?;
+                slot.reader.head.store(head, Ordering::Relaxed);
+                Some(slot.reader.clone())
+            }
+        }
     }
 
     /// Removes the specified reader
     fn remove(&self, reader: &Arc<Reader>) {
-        let mut lock = self.0.write().expect("another thread panicked");
-        lock.retain(|test_reader| !Arc::ptr_eq(test_reader, reader));
+        match &self.readers {
+            Readers::Dynamic(readers) => {
+                let mut lock = readers.write().expect("another thread panicked");
+                lock.retain(|test_reader| !Arc::ptr_eq(test_reader, reader));
+            }
+            Readers::Fixed(slots) => {
+                let slot = slots
+                    .iter()
+                    .find(|slot| Arc::ptr_eq(&slot.reader, reader))
+                    .unwrap();
+                slot.reader.waker.take();
+                slot.used.store(false, Ordering::Relaxed);
+            }
+        }
     }
 
-    /// Return the earliest head between all threads
-    fn earliest_head(&self) -> u64 {
-        let lock = self.0.read().expect("another thread panicked");
-        lock.iter()
-            .map(|reader| reader.head.load(Ordering::Relaxed))
-            .min()
-            .unwrap()
+    /// Return the earliest head between all threads, or `None` if there are no readers
+    fn earliest_head(&self) -> Option<u64> {
+        // Readers store their heads, or remove themselves, before finding the earliest head.
+        // Without this, two readers may each observe the other's stale head, and the stream's
+        // head never advances past either of them.
+        // The read-modify-write ensures the later reader observes the earlier reader's changes.
+        self.epoch.fetch_add(1, Ordering::AcqRel);
+
+        match &self.readers {
+            Readers::Dynamic(readers) => {
+                let lock = readers.read().expect("another thread panicked");
+                lock.iter()
+                    .map(|reader| reader.head.load(Ordering::Acquire))
+                    .min()
+            }
+            Readers::Fixed(slots) => slots
+                .iter()
+                .filter(|slot| slot.used.load(Ordering::Relaxed))
+                .map(|slot| slot.reader.head.load(Ordering::Acquire))
+                .min(),
+        }
     }
 }
 
@@ -66,7 +196,17 @@ impl Waker {
 /// [`SplittableView::into_cloneable_view`](`super::SplittableView::into_cloneable_view`).
 ///
 /// This view may be cloned to be used with other readers.  The cloned view is initialized with
-/// the same view of the stream.
+/// the same view of the stream, including its mark.
+///
+/// Views created by
+/// [`into_cloneable_view_with_max_readers`](`super::SplittableView::into_cloneable_view_with_max_readers`)
+/// preallocate every reader, so waking readers never locks or allocates.
+///
+/// Each reader is only woken once the data requested by its grant is available, or the stream
+/// is closed, so releasing small amounts of data doesn't wake readers waiting for large grants.
+///
+/// This view supports marking a position in the stream with [`mark`](`Self::mark`), and
+/// returning to it with [`rewind`](`Self::rewind`).
 pub struct Cloneable<T>
 where
     T: SplittableView,
@@ -76,6 +216,7 @@ where
     waker: Arc<Waker>,
     head: u64,
     len: usize,
+    mark: Option<u64>,
 }
 
 impl<T> Cloneable<T>
@@ -83,46 +224,125 @@ where
     T: SplittableView,
 {
     pub(crate) fn new(splittable: T) -> Self {
-        let waker = Arc::new(Waker::new());
+        Self::with_waker(splittable, Waker::new())
+    }
+
+    pub(crate) fn with_max_readers(splittable: T, max_readers: usize) -> Self {
+        Self::with_waker(splittable, Waker::with_max_readers(max_readers))
+    }
+
+    fn with_waker(splittable: T, waker: Waker) -> Self {
+        let waker = Arc::new(waker);
         // Safety: we have unique ownership of `splittable`
         let splittable = unsafe {
             let waker = waker.clone();
-            splittable.set_reader_waker(move || waker.wake());
+            splittable.set_reader_waker_with_end(move |end| waker.wake(end));
             Arc::pin(splittable)
         };
-        let this_reader = waker.0.read().expect("another thread panicked")[0].clone();
+        let this_reader = waker.first();
         Self {
             splittable,
             this_reader,
             waker,
             head: 0,
             len: 0,
+            mark: None,
         }
     }
-}
 
-impl<T> Clone for Cloneable<T>
-where
-    T: SplittableView,
-{
-    fn clone(&self) -> Self {
-        let this_reader = self.waker.insert(&self.this_reader);
-        Self {
+    /// Mark the current position in the stream.
+    ///
+    /// Released data after the mark is retained, and can be returned to with
+    /// [`rewind`](`Self::rewind`), until the mark is removed with [`unmark`](`Self::unmark`).
+    /// If a mark already exists, it is replaced.
+    ///
+    /// Retained data counts towards the grants requested from the stream, so grants may exceed
+    /// the maximum possible grant sooner.
+    pub fn mark(&mut self) {
+        self.mark = Some(self.head);
+        self.set_reader_head(self.head);
+    }
+
+    /// Return to the marked position in the stream, keeping the mark.
+    ///
+    /// # Panics
+    /// Panics if there is no mark.
+    pub fn rewind(&mut self) {
+        let mark = self.mark.expect("attempted to rewind without a mark");
+        let retained: usize = (self.head - mark).try_into().unwrap();
+        self.len += retained;
+        self.head = mark;
+    }
+
+    /// Remove the mark, if there is one, and stop retaining released data.
+    pub fn unmark(&mut self) {
+        if self.mark.take().is_some() {
+            self.set_reader_head(self.head);
+        }
+    }
+
+    /// Attempt to clone this view.
+    ///
+    /// Returns `None` if the view was created by
+    /// [`into_cloneable_view_with_max_readers`](`super::SplittableView::into_cloneable_view_with_max_readers`)
+    /// and the maximum number of readers already exists.
+    pub fn try_clone(&self) -> Option<Self> {
+        let this_reader = self.waker.insert(&self.this_reader)?;
+        Some(Self {
             splittable: self.splittable.clone(),
             this_reader,
             waker: self.waker.clone(),
             head: self.head,
             len: self.len,
+            mark: self.mark,
+        })
+    }
+
+    /// The position and length of data retained by the mark.
+    fn retained(&self) -> (u64, usize) {
+        let start = self.mark.unwrap_or(self.head);
+        (start, (self.head - start).try_into().unwrap())
+    }
+
+    /// Set the earliest position this reader will read.
+    fn set_reader_head(&self, head: u64) {
+        // Release the data before the head to whichever reader advances the stream
+        self.this_reader.head.store(head, Ordering::Release);
+
+        // Safety: we never read earlier than this head value with this reader
+        unsafe {
+            self.splittable
+                .as_ref()
+                .compare_set_head(self.waker.earliest_head().unwrap());
         }
     }
 }
 
+impl<T> Clone for Cloneable<T>
+where
+    T: SplittableView,
+{
+    /// # Panics
+    /// Panics if the maximum number of readers already exists.
+    /// See [`try_clone`](`Self::try_clone`).
+    fn clone(&self) -> Self {
+        self.try_clone()
+            .expect("attempted to exceed the maximum number of readers")
+    }
+}
+
 impl<T> Drop for Cloneable<T>
 where
     T: SplittableView,
 {
     fn drop(&mut self) {
         self.waker.remove(&self.this_reader);
+
+        // Release any data that was only retained for this reader.
+        if let Some(head) = self.waker.earliest_head() {
+            // Safety: the remaining readers never read earlier than their heads
+            unsafe { self.splittable.as_ref().compare_set_head(head) };
+        }
     }
 }
 
@@ -143,14 +363,17 @@ where
         cx: &mut Context,
         count: usize,
     ) -> Poll<Result<(), Self::Error>> {
+        // Request from the mark, so the retained data is included in the grant.
+        let (start, retained) = self.retained();
+        let wake_at = self.head + u64::try_from(count).unwrap();
         match self.splittable.as_ref().poll_available(
             cx,
-            |waker| self.this_reader.waker.register(waker),
-            self.head,
-            count,
+            |waker| self.this_reader.register(waker, wake_at),
+            start,
+            retained + count,
         ) {
             Poll::Ready(Ok(len)) => {
-                self.len = len;
+                self.len = len - retained;
                 Poll::Ready(Ok(()))
             }
             Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
@@ -158,14 +381,19 @@ where
         }
     }
 
-    fn try_grant(self: &mut Self, count: usize) -> Result<bool, Self::Error> {
-        match self.splittable.as_ref().try_available(self.head, count) {
+    fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
+        let (start, retained) = self.retained();
+        match self
+            .splittable
+            .as_ref()
+            .try_available(start, retained + count)
+        {
             Ok(0) => Ok(false),
             Ok(len) => {
-                self.len = len;
+                self.len = len - retained;
                 Ok(true)
-            },
-            Err(e) => Err(e)
+            }
+            Err(e) => Err(e),
         }
     }
 
@@ -173,13 +401,8 @@ where
         self.len -= count;
         let count: u64 = count.try_into().unwrap();
         self.head += count;
-        self.this_reader.head.store(self.head, Ordering::Relaxed);
-
-        // Safety: we never read earlier than this head value with this reader
-        unsafe {
-            self.splittable
-                .as_ref()
-                .compare_set_head(self.waker.earliest_head());
+        if self.mark.is_none() {
+            self.set_reader_head(self.head);
         }
     }
 }
@@ -1,14 +1,14 @@
 use crate::splittable::{
     SplittableView, SplittableViewImpl, SplittableViewImplMut, SplittableViewMut,
 };
+use crate::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc, Mutex,
+};
 use once_cell::sync::OnceCell;
 use std::{
     convert::TryInto,
     pin::Pin,
-    sync::{
-        atomic::{AtomicBool, AtomicU64, Ordering},
-        Arc, Mutex,
-    },
     task::{Context, Poll, Waker},
 };
 
@@ -32,6 +32,9 @@ where
     )
 }
 
+/// Wakes `Second`, passing the head of `First`.
+type WakeFn = Box<dyn Fn(Option<u64>) + Send + Sync + 'static>;
+
 struct Shared<T>
 where
     T: SplittableView,
@@ -39,7 +42,7 @@ where
     splittable: T,
     head: AtomicU64,
     closed: AtomicBool,
-    waker: Mutex<Option<Box<dyn Fn() + Send + Sync + 'static>>>,
+    waker: Mutex<Option<WakeFn>>,
 }
 
 /// The first `SplittableView` produced by [`sequence`](`crate::SplittableView::sequence`).
@@ -48,7 +51,7 @@ where
     T: SplittableView,
 {
     shared: Arc<Shared<T>>,
-    waker: OnceCell<Box<dyn Fn() + Send + Sync + 'static>>,
+    waker: OnceCell<WakeFn>,
 }
 
 impl<T> Drop for First<T>
@@ -56,8 +59,9 @@ where
     T: SplittableView,
 {
     fn drop(&mut self) {
-        self.shared.closed.store(true, Ordering::Relaxed);
-        self.wake_second()
+        // Release the final head to `Second`
+        self.shared.closed.store(true, Ordering::Release);
+        self.wake_second(None)
     }
 }
 
@@ -65,12 +69,29 @@ impl<T> First<T>
 where
     T: SplittableView,
 {
-    fn wake_second(&self) {
+    // Wake `Second`, passing the head, or `None` if this is closed.
+    fn wake_second(&self, head: Option<u64>) {
         if let Ok(waker) = self.waker.get_or_try_init(|| {
             let mut lock = self.shared.waker.lock().expect("another thread panicked");
             lock.take().ok_or(())
         }) {
-            waker()
+            waker(head)
+        }
+    }
+
+    /// Make the head available to `Second`, or advance the underlying view if `Second` was
+    /// dropped.
+    ///
+    /// # Safety
+    /// See [`SplittableViewImpl::compare_set_head`].
+    unsafe fn publish_head(&self, index: u64) {
+        // `Second` may be dropped concurrently, after loading a stale head.
+        // Checking `closed` with a read-modify-write ensures that either `Second` observes the new
+        // head when it's dropped, or this observes that `Second` was dropped.
+        if self.shared.closed.fetch_or(false, Ordering::AcqRel) {
+            self.shared.splittable.compare_set_head(index);
+        } else {
+            self.wake_second(Some(index));
         }
     }
 }
@@ -86,38 +107,37 @@ where
         self.shared.splittable.set_reader_waker(waker);
     }
 
+    unsafe fn set_reader_waker_with_end(
+        &self,
+        waker: impl Fn(Option<u64>) + Send + Sync + 'static,
+    ) {
+        self.shared.splittable.set_reader_waker_with_end(waker);
+    }
+
     unsafe fn set_head(&self, index: u64) {
-        if self.shared.closed.load(Ordering::Relaxed) {
-            // This may overlap with a drop of `Second`, so always use `compare_set_head`.
-            self.shared.splittable.compare_set_head(index);
-        } else {
-            self.shared.head.store(index, Ordering::Relaxed);
-            self.wake_second();
-        }
+        // Release the data before the head to `Second`
+        self.shared.head.store(index, Ordering::Release);
+        self.publish_head(index);
     }
 
     unsafe fn compare_set_head(&self, index: u64) {
-        if self.shared.closed.load(Ordering::Relaxed) {
-            self.shared.splittable.compare_set_head(index);
-        } else {
-            // only set the head if it's greater than the current head
-            let mut current = self.shared.head.load(Ordering::Relaxed);
-            if index > current {
-                while let Err(previous) = self.shared.head.compare_exchange_weak(
-                    current,
-                    index,
-                    Ordering::Relaxed,
-                    Ordering::Relaxed,
-                ) {
-                    if index > previous {
-                        current = previous
-                    } else {
-                        break;
-                    }
+        // only set the head if it's greater than the current head
+        let mut current = self.shared.head.load(Ordering::Relaxed);
+        if index > current {
+            while let Err(previous) = self.shared.head.compare_exchange_weak(
+                current,
+                index,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                if index > previous {
+                    current = previous
+                } else {
+                    break;
                 }
             }
-            self.wake_second()
         }
+        self.publish_head(self.shared.head.load(Ordering::Relaxed));
     }
 
     fn poll_available(
@@ -130,7 +150,7 @@ where
         Pin::new(&self.shared.splittable).poll_available(cx, register_wakeup, index, len)
     }
 
-    fn try_available(self: &Self, index: u64, len: usize) -> Result<usize, Self::Error> {
+    fn try_available(&self, index: u64, len: usize) -> Result<usize, Self::Error> {
         self.shared.splittable.try_available(index, len)
     }
 
@@ -161,15 +181,17 @@ where
     T: SplittableView,
 {
     fn drop(&mut self) {
-        self.shared.closed.store(true, Ordering::Relaxed);
+        // Synchronizes with `First::publish_head`, so the head loaded here is up to date, or
+        // `First` advances the underlying view itself.
+        self.shared.closed.swap(true, Ordering::AcqRel);
 
         // Safety: this view is done with `head` so we can drop up to it.
         // We must use `compare_set_head` since this may overlap with an advance on `First` that
-        // happens after `closed` is set but before setting the head occurs.
+        // happens after `closed` is set.
         unsafe {
             self.shared
                 .splittable
-                .compare_set_head(self.shared.head.load(Ordering::Relaxed));
+                .compare_set_head(self.shared.head.load(Ordering::Acquire));
         }
     }
 }
@@ -179,7 +201,7 @@ where
     T: SplittableView,
 {
     fn readable_len(&self, start: u64) -> usize {
-        (self.shared.head.load(Ordering::Relaxed) - start)
+        (self.shared.head.load(Ordering::Acquire) - start)
             .try_into()
             .unwrap()
     }
@@ -193,6 +215,13 @@ where
     type Error = T::Error;
 
     unsafe fn set_reader_waker(&self, waker: impl Fn() + Send + Sync + 'static) {
+        self.set_reader_waker_with_end(move |_| waker());
+    }
+
+    unsafe fn set_reader_waker_with_end(
+        &self,
+        waker: impl Fn(Option<u64>) + Send + Sync + 'static,
+    ) {
         let mut lock = self.shared.waker.lock().expect("another thread panicked!");
         *lock = Some(Box::new(waker));
     }
@@ -221,22 +250,27 @@ where
         } else {
             register_wakeup(cx.waker());
             let available = self.readable_len(index);
-            if available >= len || self.shared.closed.load(Ordering::Relaxed) {
+            if available >= len {
                 Poll::Ready(Ok(available))
+            } else if self.shared.closed.load(Ordering::Acquire) {
+                // `First` may have advanced the head before it was dropped.
+                Poll::Ready(Ok(self.readable_len(index)))
             } else {
                 Poll::Pending
             }
         }
     }
 
-    fn try_available(self: &Self, index: u64, len: usize) -> Result<usize, Self::Error> {
+    fn try_available(&self, index: u64, len: usize) -> Result<usize, Self::Error> {
         let available = self.readable_len(index);
         if available >= len {
             Ok(available)
         } else {
             let available = self.readable_len(index);
-            if available >= len || self.shared.closed.load(Ordering::Relaxed) {
+            if available >= len {
                 Ok(available)
+            } else if self.shared.closed.load(Ordering::Acquire) {
+                Ok(self.readable_len(index))
             } else {
                 Ok(0)
             }
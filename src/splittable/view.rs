@@ -1,13 +1,15 @@
 use super::{SplittableView, SplittableViewMut};
-use futures::task::AtomicWaker;
+use crate::sync::{Arc, AtomicWaker};
 use std::{
     convert::TryInto,
     pin::Pin,
-    sync::Arc,
     task::{Context, Poll},
 };
 
 /// A view returned by [`SplittableView::into_view`](`super::SplittableView::into_view`).
+///
+/// This view supports marking a position in the stream with [`mark`](`Self::mark`), and
+/// returning to it with [`rewind`](`Self::rewind`).
 pub struct View<T>
 where
     T: SplittableView,
@@ -16,6 +18,7 @@ where
     waker: Arc<AtomicWaker>,
     head: u64,
     len: usize,
+    mark: Option<u64>,
 }
 
 impl<T> View<T>
@@ -34,8 +37,52 @@ where
             waker,
             head: 0,
             len: 0,
+            mark: None,
         }
     }
+
+    /// Mark the current position in the stream.
+    ///
+    /// Released data after the mark is retained, and can be returned to with
+    /// [`rewind`](`Self::rewind`), until the mark is removed with [`unmark`](`Self::unmark`).
+    /// If a mark already exists, it is replaced.
+    ///
+    /// Retained data counts towards the grants requested from the stream, so grants may exceed
+    /// the maximum possible grant sooner.
+    pub fn mark(&mut self) {
+        self.mark = Some(self.head);
+        // Safety: we never read earlier than this head value
+        unsafe {
+            self.splittable.set_head(self.head);
+        }
+    }
+
+    /// Return to the marked position in the stream, keeping the mark.
+    ///
+    /// # Panics
+    /// Panics if there is no mark.
+    pub fn rewind(&mut self) {
+        let mark = self.mark.expect("attempted to rewind without a mark");
+        let retained: usize = (self.head - mark).try_into().unwrap();
+        self.len += retained;
+        self.head = mark;
+    }
+
+    /// Remove the mark, if there is one, and stop retaining released data.
+    pub fn unmark(&mut self) {
+        if self.mark.take().is_some() {
+            // Safety: we never read earlier than this head value
+            unsafe {
+                self.splittable.set_head(self.head);
+            }
+        }
+    }
+
+    /// The position and length of data retained by the mark.
+    fn retained(&self) -> (u64, usize) {
+        let start = self.mark.unwrap_or(self.head);
+        (start, (self.head - start).try_into().unwrap())
+    }
 }
 
 impl<T> crate::View for View<T>
@@ -55,14 +102,16 @@ where
         cx: &mut Context,
         count: usize,
     ) -> Poll<Result<(), Self::Error>> {
+        // Request from the mark, so the retained data is included in the grant.
+        let (start, retained) = self.retained();
         match Pin::new(&self.splittable).poll_available(
             cx,
             |waker| self.waker.register(waker),
-            self.head,
-            count,
+            start,
+            retained + count,
         ) {
             Poll::Ready(Ok(len)) => {
-                self.len = len;
+                self.len = len - retained;
                 Poll::Ready(Ok(()))
             }
             Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
@@ -70,14 +119,15 @@ where
         }
     }
 
-    fn try_grant(self: &mut Self, count: usize) -> Result<bool, Self::Error> {
-        match self.splittable.try_available(self.head, count) {
+    fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
+        let (start, retained) = self.retained();
+        match self.splittable.try_available(start, retained + count) {
             Ok(0) => Ok(false),
             Ok(len) => {
-                self.len = len;
+                self.len = len - retained;
                 Ok(true)
-            },
-            Err(e) => Err(e)
+            }
+            Err(e) => Err(e),
         }
     }
 
@@ -90,9 +140,11 @@ where
         self.len -= count;
         let count: u64 = count.try_into().unwrap();
         self.head += count;
-        // Safety: we never read earlier than this head value
-        unsafe {
-            self.splittable.set_head(self.head);
+        if self.mark.is_none() {
+            // Safety: we never read earlier than this head value
+            unsafe {
+                self.splittable.set_head(self.head);
+            }
         }
     }
 }
@@ -0,0 +1,36 @@
+//! Synchronization primitives used by the lock-free streams.
+//!
+//! When built with `--cfg loom`, these are replaced by their [`loom`](https://docs.rs/loom)
+//! equivalents, so the concurrency of the streams can be model checked.
+
+#[cfg(not(loom))]
+pub(crate) use futures::task::AtomicWaker;
+#[cfg(not(loom))]
+pub(crate) use std::sync::{atomic, Arc, Mutex, RwLock};
+
+#[cfg(loom)]
+pub(crate) use loom::sync::{atomic, Arc, Mutex, RwLock};
+
+/// An `AtomicWaker` with the same interface as the one provided by `futures`.
+#[cfg(loom)]
+#[derive(Debug, Default)]
+pub(crate) struct AtomicWaker(loom::future::AtomicWaker);
+
+#[cfg(loom)]
+impl AtomicWaker {
+    pub(crate) fn new() -> Self {
+        Self(loom::future::AtomicWaker::new())
+    }
+
+    pub(crate) fn register(&self, waker: &core::task::Waker) {
+        self.0.register_by_ref(waker)
+    }
+
+    pub(crate) fn wake(&self) {
+        self.0.wake()
+    }
+
+    pub(crate) fn take(&self) -> Option<core::task::Waker> {
+        self.0.take_waker()
+    }
+}
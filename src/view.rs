@@ -25,6 +25,25 @@ where
     }
 }
 
+/// How [`View::blocking_grant_with`] waits for a grant.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BlockingStrategy {
+    /// Spin until the grant is available.
+    ///
+    /// This has the lowest latency, but occupies the CPU while waiting.
+    Spin,
+
+    /// Spin the specified number of times, then yield the thread to the scheduler between checks.
+    SpinThenYield(u32),
+
+    /// Spin the specified number of times, then park the thread until the stream wakes it.
+    ///
+    /// With no spins, this is identical to [`View::blocking_grant`].
+    SpinThenPark(u32),
+}
+
 /// Obtain views into asynchronous contiguous-memory streams.
 pub trait View: Sized + Unpin {
     /// The streamed type.
@@ -54,10 +73,7 @@ pub trait View: Sized + Unpin {
     /// Attempt to obtain a view of at least `count` elements.
     ///
     /// If the request exceeds the maximum possible grant (if there is one), an error should be returned.
-    fn try_grant(
-        self: &mut Self,
-        count: usize,
-    ) -> Result<bool, Self::Error>;
+    fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error>;
 
     /// Attempt to advance past the first `count` elements in the current view.
     ///
@@ -82,6 +98,43 @@ pub trait View: Sized + Unpin {
         futures::executor::block_on(self.grant(count))
     }
 
+    /// Obtains a view of at least `count` elements, blocking the current thread using the
+    /// specified strategy.
+    ///
+    /// Spinning avoids the cost of parking and unparking the thread, which may be significant for
+    /// latency-sensitive threads.
+    ///
+    /// See [`poll_grant`](`View::poll_grant`).
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "std"))))]
+    fn blocking_grant_with(
+        &mut self,
+        count: usize,
+        strategy: BlockingStrategy,
+    ) -> Result<(), Self::Error> {
+        // Polling with a no-op waker checks the stream, including whether it's closed, without
+        // arranging to be woken.
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        let mut spins = 0;
+        loop {
+            if let Poll::Ready(result) = Pin::new(&mut *self).poll_grant(&mut cx, count) {
+                return result;
+            }
+            match strategy {
+                BlockingStrategy::Spin => {}
+                BlockingStrategy::SpinThenYield(max_spins) if spins >= max_spins => {
+                    std::thread::yield_now();
+                    continue;
+                }
+                BlockingStrategy::SpinThenPark(max_spins) if spins >= max_spins => {
+                    return self.blocking_grant(count);
+                }
+                _ => spins += 1,
+            }
+            core::hint::spin_loop();
+        }
+    }
+
     /// Maps this view to a new view producing error `E`.
     fn map_error<E, F>(self, f: F) -> MapError<Self, E, F>
     where
@@ -93,6 +146,16 @@ pub trait View: Sized + Unpin {
             _error: core::marker::PhantomData,
         }
     }
+
+    /// Maps this view to a new view producing error `E`, using `Into`.
+    ///
+    /// This is useful for converting to a common error type, such as [`Error`](crate::Error).
+    fn err_into<E>(self) -> MapError<Self, E, fn(Self::Error) -> E>
+    where
+        Self::Error: Into<E>,
+    {
+        self.map_error(Into::into)
+    }
 }
 
 impl<S: View> View for &mut S {
@@ -111,7 +174,7 @@ impl<S: View> View for &mut S {
         S::poll_grant(Pin::new(&mut **self), cx, count)
     }
 
-    fn try_grant(self: &mut Self, count: usize) -> Result<bool, Self::Error> {
+    fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
         S::try_grant(&mut **self, count)
     }
 
@@ -175,7 +238,7 @@ where
         pinned.view.poll_grant(cx, count).map(|r| r.map_err(f))
     }
 
-    fn try_grant(self: &mut Self, count: usize) -> Result<bool, Self::Error> {
+    fn try_grant(&mut self, count: usize) -> Result<bool, Self::Error> {
         self.view.try_grant(count).map_err(&self.map)
     }
 
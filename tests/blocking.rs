@@ -0,0 +1,50 @@
+use rivulet::{circular_buffer, view::BlockingStrategy, SplittableView, View, ViewMut};
+
+fn transfer(strategy: BlockingStrategy) {
+    let (mut sink, source) = circular_buffer::<u32>(4096);
+    let mut source = source.into_view();
+
+    let writer = std::thread::spawn(move || {
+        for i in 0..1000 {
+            sink.blocking_grant_with(77, strategy).unwrap();
+            for (j, value) in sink.view_mut()[..77].iter_mut().enumerate() {
+                *value = i * 77 + j as u32;
+            }
+            sink.release(77);
+        }
+    });
+
+    let mut count = 0;
+    loop {
+        // The end of the stream is detected, even when spinning.
+        source.blocking_grant_with(100, strategy).unwrap();
+        let view = source.view();
+        if view.is_empty() {
+            break;
+        }
+        for value in view {
+            assert_eq!(*value, count);
+            count += 1;
+        }
+        let len = view.len();
+        source.release(len);
+    }
+    writer.join().unwrap();
+    assert_eq!(count, 77 * 1000);
+}
+
+#[test]
+fn blocking_spin() {
+    transfer(BlockingStrategy::Spin);
+}
+
+#[test]
+fn blocking_spin_then_yield() {
+    transfer(BlockingStrategy::SpinThenYield(100));
+}
+
+#[test]
+fn blocking_spin_then_park() {
+    transfer(BlockingStrategy::SpinThenPark(100));
+    transfer(BlockingStrategy::SpinThenPark(0));
+}
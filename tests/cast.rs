@@ -0,0 +1,73 @@
+use rivulet::{
+    cast::{Bytes, Cast},
+    circular_buffer, SplittableView, View, ViewMut,
+};
+
+async fn write<T: ViewMut<Item = [f32; 3]> + Send>(mut sink: T, block: usize, count: usize) {
+    for i in 0..count {
+        sink.grant(block).await.unwrap();
+        for (j, value) in sink.view_mut()[..block].iter_mut().enumerate() {
+            let x = (i * block + j) as f32;
+            *value = [x, -x, x * 2.];
+        }
+        sink.release(block);
+    }
+}
+
+async fn read<T: View<Item = [f32; 3]> + Send>(mut source: T, block: usize) -> usize {
+    let mut count = 0;
+    loop {
+        source.grant(block).await.unwrap();
+        let view = source.view();
+        if view.is_empty() {
+            break count;
+        }
+        for value in view {
+            let x = count as f32;
+            assert_eq!(*value, [x, -x, x * 2.]);
+            count += 1;
+        }
+        let len = view.len();
+        source.release(len);
+    }
+}
+
+#[tokio::test]
+async fn cast_circular_buffer() {
+    let (sink, source) = circular_buffer::<u8>(4096);
+
+    // Writes and reads wrap around the buffer many times.
+    let write = tokio::spawn(write(Cast::new(sink), 100, 1000));
+    let read = tokio::spawn(read(Cast::new(source.into_view()), 77));
+
+    let (write, read) = tokio::join!(write, read);
+    write.unwrap();
+    assert_eq!(read.unwrap(), 100 * 1000);
+}
+
+#[test]
+fn bytes() {
+    let storage: Vec<u16> = (0..100).collect();
+    let mut stream = Bytes::new(rivulet::slice::Slice::new(&storage).into_view());
+
+    stream.blocking_grant(1).unwrap();
+    assert_eq!(stream.view().len(), 200);
+    assert_eq!(
+        &stream.view()[..4],
+        [0u16.to_ne_bytes(), 1u16.to_ne_bytes()].concat()
+    );
+    stream.release(4);
+
+    let mut stream = Cast::<_, u16>::new(stream);
+    stream.blocking_grant(1).unwrap();
+    assert_eq!(stream.view(), &storage[2..]);
+}
+
+#[test]
+#[should_panic]
+fn bytes_partial_release() {
+    let storage: Vec<u16> = (0..100).collect();
+    let mut stream = Bytes::new(rivulet::slice::Slice::new(&storage).into_view());
+    stream.blocking_grant(1).unwrap();
+    stream.release(1);
+}
@@ -1,5 +1,9 @@
 use rand::{rngs::SmallRng, Rng, SeedableRng};
-use rivulet::{circular_buffer, SplittableView, View, ViewMut};
+use rivulet::{
+    circular_buffer,
+    circular_buffer::{lossy_circular_buffer, spilling_circular_buffer, LossyError},
+    SplittableView, View, ViewMut,
+};
 use std::hash::Hasher;
 
 static BUFFER_SIZE: usize = 4096;
@@ -63,3 +67,291 @@ async fn multiple_reader_buffer_integrity() {
         assert_eq!(write_hash.as_ref().unwrap(), read_hash.as_ref().unwrap());
     }
 }
+
+#[tokio::test]
+async fn spilling_buffer_integrity() {
+    let (sink, source) = spilling_circular_buffer::<i64>(BUFFER_SIZE).unwrap();
+    let source = source.into_cloneable_view();
+
+    let write_hash = tokio::spawn(write(sink, 500, 400));
+    let read_hashes = (0..10)
+        .map(|_| tokio::spawn(read(source.clone())))
+        .collect::<Vec<_>>();
+    std::mem::drop(source); // remaining reference doesn't get used, so drop it
+
+    let (write_hash, read_hashes) =
+        futures::future::join(write_hash, futures::future::join_all(read_hashes)).await;
+    for read_hash in read_hashes {
+        assert_eq!(write_hash.as_ref().unwrap(), read_hash.as_ref().unwrap());
+    }
+}
+
+#[test]
+fn spilling_buffer_stalled_reader() {
+    let (mut sink, source) = spilling_circular_buffer::<u32>(BUFFER_SIZE).unwrap();
+    let mut source = source.into_view();
+
+    // Write much more than the buffer can hold, without reading.
+    let count = BUFFER_SIZE as u32 * 10;
+    for i in 0..count {
+        sink.blocking_grant(1).unwrap();
+        sink.view_mut()[0] = i;
+        sink.release(1);
+    }
+    std::mem::drop(sink);
+
+    // All of the data should arrive, in order.
+    let mut expected = 0;
+    loop {
+        source.blocking_grant(BUFFER_SIZE).unwrap();
+        let view = source.view();
+        if view.is_empty() {
+            break;
+        }
+        for value in view {
+            assert_eq!(*value, expected);
+            expected += 1;
+        }
+        let len = view.len();
+        source.release(len);
+    }
+    assert_eq!(expected, count);
+}
+
+#[test]
+fn spilling_buffer_idle_writer() {
+    let (mut sink, source) = spilling_circular_buffer::<u32>(BUFFER_SIZE).unwrap();
+    let mut source = source.into_view();
+
+    let count = BUFFER_SIZE as u32 * 10;
+    for i in 0..count {
+        sink.blocking_grant(1).unwrap();
+        sink.view_mut()[0] = i;
+        sink.release(1);
+    }
+
+    // Spilled data is refilled as the reader frees space, even if the writer is idle.
+    let mut expected = 0;
+    while expected < count {
+        source.blocking_grant(1).unwrap();
+        let view = source.view();
+        for value in view {
+            assert_eq!(*value, expected);
+            expected += 1;
+        }
+        let len = view.len();
+        source.release(len);
+    }
+    std::mem::drop(sink);
+    source.blocking_grant(1).unwrap();
+    assert!(source.view().is_empty());
+}
+
+fn write_integers<T: ViewMut<Item = u64>>(mut sink: T, count: usize) {
+    let mut rng = SmallRng::from_entropy();
+    let mut next = 0;
+    while next < count {
+        sink.blocking_grant(1).unwrap();
+        let len = sink
+            .view()
+            .len()
+            .min(rng.gen_range(1..64))
+            .min(count - next);
+        for value in &mut sink.view_mut()[..len] {
+            *value = next as u64;
+            next += 1;
+        }
+        sink.release(len);
+    }
+}
+
+fn write_strings<T: ViewMut<Item = String>>(mut sink: T, count: usize) {
+    let mut rng = SmallRng::from_entropy();
+    let mut next = 0;
+    while next < count {
+        sink.blocking_grant(1).unwrap();
+        let len = sink
+            .view()
+            .len()
+            .min(rng.gen_range(1..64))
+            .min(count - next);
+        for value in &mut sink.view_mut()[..len] {
+            // Replacing the string frees the previous allocation, which a reader may have used.
+            *value = next.to_string();
+            next += 1;
+        }
+        sink.release(len);
+    }
+}
+
+fn read_strings<T: View<Item = String>>(mut source: T) -> usize {
+    let mut rng = SmallRng::from_entropy();
+    let mut expected = 0;
+    loop {
+        source.blocking_grant(rng.gen_range(1..64)).unwrap();
+        if source.view().is_empty() {
+            break expected;
+        }
+        for value in source.view() {
+            assert_eq!(*value, expected.to_string());
+            expected += 1;
+        }
+        let released = source.view().len();
+        source.release(released);
+    }
+}
+
+#[test]
+fn string_buffer_integrity() {
+    const COUNT: usize = 1_000_000;
+    let (sink, source) = circular_buffer::<String>(256);
+    let source = source.into_cloneable_view_with_max_readers(4);
+
+    let readers = (0..3)
+        .map(|_| {
+            let source = source.clone();
+            std::thread::spawn(move || read_strings(source))
+        })
+        .collect::<Vec<_>>();
+    let writer = std::thread::spawn(move || write_strings(sink, COUNT));
+    assert_eq!(read_strings(source), COUNT);
+
+    writer.join().unwrap();
+    for reader in readers {
+        assert_eq!(reader.join().unwrap(), COUNT);
+    }
+}
+
+#[test]
+fn string_sequence_integrity() {
+    const COUNT: usize = 1_000_000;
+    let (sink, source) = circular_buffer::<String>(256);
+    let (first, second) = source.sequence();
+
+    let writer = std::thread::spawn(move || write_strings(sink, COUNT));
+    let first = std::thread::spawn(move || read_strings(first.into_view()));
+    assert_eq!(read_strings(second.into_view()), COUNT);
+
+    writer.join().unwrap();
+    assert_eq!(first.join().unwrap(), COUNT);
+}
+
+#[test]
+fn lossy_buffer_overrun() {
+    let (mut sink, mut source) = lossy_circular_buffer::<u32>(BUFFER_SIZE);
+    let capacity = sink.try_grant(usize::MAX).unwrap_err().0 as u32;
+
+    // Write more than the buffer can hold, without reading.
+    let count = capacity * 2 + 10;
+    for i in 0..count {
+        assert!(sink.try_grant(1).unwrap());
+        sink.view_mut()[0] = i;
+        sink.release(1);
+    }
+    std::mem::drop(sink);
+
+    // The reader skips to the oldest retained data.
+    assert!(matches!(
+        source.blocking_grant(1),
+        Err(LossyError::Overrun(lost)) if lost == u64::from(count - capacity)
+    ));
+    let mut expected = count - capacity;
+    loop {
+        source.blocking_grant(1).unwrap();
+        let view = source.view();
+        if view.is_empty() {
+            break;
+        }
+        for value in view {
+            assert_eq!(*value, expected);
+            expected += 1;
+        }
+        let len = view.len();
+        source.release(len);
+    }
+    assert_eq!(expected, count);
+}
+
+#[test]
+fn lossy_buffer_reader_dropped_after_overrun() {
+    let (mut sink, source) = lossy_circular_buffer::<u32>(BUFFER_SIZE);
+    let capacity = sink.try_grant(usize::MAX).unwrap_err().0;
+
+    for _ in 0..3 {
+        sink.blocking_grant(capacity).unwrap();
+        sink.release(capacity);
+    }
+    std::mem::drop(source);
+
+    // The writer keeps writing without overwriting, as with any closed buffer.
+    for _ in 0..3 {
+        assert!(sink.try_grant(1).unwrap());
+        assert!(sink.view().len() <= capacity);
+        let len = sink.view().len();
+        sink.release(len);
+    }
+}
+
+#[test]
+fn lossy_buffer_granted_data_copied() {
+    let (mut sink, mut source) = lossy_circular_buffer::<u32>(BUFFER_SIZE);
+    let capacity = sink.try_grant(usize::MAX).unwrap_err().0;
+
+    sink.blocking_grant(capacity).unwrap();
+    for (i, value) in sink.view_mut().iter_mut().enumerate() {
+        *value = i as u32;
+    }
+    sink.release(capacity);
+    source.blocking_grant(capacity).unwrap();
+
+    // The writer never waits, and overwriting the buffer doesn't change the granted view.
+    for _ in 0..3 {
+        assert!(sink.try_grant(capacity).unwrap());
+        sink.view_mut().fill(u32::MAX);
+        sink.release(capacity);
+    }
+    assert!(source.view().iter().copied().eq(0..capacity as u32));
+    source.release(capacity);
+
+    // Only the data the reader never copied is lost.
+    assert!(matches!(
+        source.try_grant(1),
+        Err(LossyError::Overrun(lost)) if lost == 2 * capacity as u64
+    ));
+    source.blocking_grant(capacity).unwrap();
+    assert!(source.view().iter().all(|value| *value == u32::MAX));
+}
+
+#[test]
+fn lossy_buffer_integrity() {
+    const COUNT: usize = 1_000_000;
+    let (sink, mut source) = lossy_circular_buffer::<u64>(256);
+
+    let writer = std::thread::spawn(move || write_integers(sink, COUNT));
+
+    // Every element is either received in order or reported lost.
+    let mut rng = SmallRng::from_entropy();
+    let mut expected = 0;
+    loop {
+        match source.blocking_grant(rng.gen_range(1..64)) {
+            Ok(()) => {}
+            Err(LossyError::Overrun(lost)) => {
+                expected += lost as usize;
+                continue;
+            }
+            Err(e) => panic!("{}", e),
+        }
+        if source.view().is_empty() {
+            break;
+        }
+        for value in source.view() {
+            assert_eq!(*value, expected as u64);
+            expected += 1;
+        }
+        let released = source.view().len();
+        source.release(released);
+    }
+    assert_eq!(expected, COUNT);
+
+    writer.join().unwrap();
+}
@@ -0,0 +1,111 @@
+use rivulet::{circular_buffer, driver::Driver, SplittableView, View, ViewMut};
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A future that counts how many times it's polled, and never completes.
+struct CountPolls<'a>(&'a Cell<usize>);
+
+impl<'a> Future for CountPolls<'a> {
+    type Output = usize;
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context) -> Poll<usize> {
+        self.0.set(self.0.get() + 1);
+        Poll::Pending
+    }
+}
+
+#[test]
+fn driver_only_polls_woken_stages() {
+    let polls = Cell::new(0);
+    let mut driver = Driver::new();
+    driver.push(CountPolls(&polls));
+    driver.push(async { 1 });
+
+    assert_eq!(driver.turn(), 2);
+    assert_eq!(driver.try_next(), Some((1, 1)));
+    assert_eq!(driver.len(), 1);
+
+    // The stalled stage is never woken, so it isn't polled again.
+    assert_eq!(driver.turn(), 0);
+    assert_eq!(driver.try_next(), None);
+    assert_eq!(polls.get(), 1);
+}
+
+#[test]
+fn driver_circular_buffers() {
+    let (mut sink, source) = circular_buffer::<u64>(4096);
+    let (mut forward_sink, forward_source) = circular_buffer::<u64>(4096);
+    let mut source = source.into_view();
+    let mut forward_source = forward_source.into_view();
+
+    let mut driver = Driver::new();
+    driver.push(async move {
+        for i in 0..1000 {
+            sink.grant(77).await.unwrap();
+            for (j, value) in sink.view_mut()[..77].iter_mut().enumerate() {
+                *value = i * 77 + j as u64;
+            }
+            sink.release(77);
+        }
+        0
+    });
+    driver.push(async move {
+        rivulet::process::copy(&mut source, &mut forward_sink)
+            .await
+            .unwrap()
+    });
+    driver.push(async move {
+        let mut count = 0;
+        loop {
+            forward_source.grant(1).await.unwrap();
+            let view = forward_source.view();
+            if view.is_empty() {
+                break count;
+            }
+            for value in view {
+                assert_eq!(*value, count);
+                count += 1;
+            }
+            let len = view.len();
+            forward_source.release(len);
+        }
+    });
+
+    let mut outputs = driver.collect::<Vec<_>>();
+    outputs.sort();
+    assert_eq!(outputs, [(0, 0), (1, 77 * 1000), (2, 77 * 1000)]);
+}
+
+#[test]
+fn driver_external_wake() {
+    // Stages may be woken from other threads.
+    let (mut sink, source) = circular_buffer::<u8>(4096);
+    let mut source = source.into_view();
+    let writer = std::thread::spawn(move || {
+        for _ in 0..100 {
+            sink.blocking_grant(10).unwrap();
+            sink.release(10);
+        }
+    });
+
+    let mut driver = Driver::new();
+    driver.push(async move {
+        let mut count = 0;
+        loop {
+            source.grant(1).await.unwrap();
+            let len = source.view().len();
+            if len == 0 {
+                break count;
+            }
+            count += len;
+            source.release(len);
+        }
+    });
+    assert_eq!(driver.next(), Some((0, 1000)));
+    assert_eq!(driver.next(), None);
+    writer.join().unwrap();
+}
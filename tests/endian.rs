@@ -0,0 +1,87 @@
+use rivulet::{
+    circular_buffer,
+    endian::{Decode, Encode, Endianness},
+    slice::{Slice, VecSink},
+    SplittableView, View, ViewMut,
+};
+
+#[test]
+fn decode_unaligned() {
+    // Offset by one byte, so the samples are unaligned.
+    let bytes = [0xff, 0x01, 0x02, 0x03, 0x04, 0x05];
+    let mut view = Slice::new(&bytes).into_view();
+    view.blocking_grant(1).unwrap();
+    view.release(1);
+
+    let mut big = Decode::<_, u16>::new(view, Endianness::Big);
+    big.blocking_grant(2).unwrap();
+    assert_eq!(big.view(), [0x0102, 0x0304]);
+    big.release(1);
+    assert_eq!(big.view(), [0x0304]);
+
+    // The trailing partial sample is never viewed.
+    big.blocking_grant(2).unwrap();
+    assert_eq!(big.view(), [0x0304]);
+
+    let mut little = Decode::<_, u16>::new(Slice::new(&bytes[1..]).into_view(), Endianness::Little);
+    little.blocking_grant(2).unwrap();
+    assert_eq!(little.view(), [0x0201, 0x0403]);
+}
+
+#[test]
+fn encode() {
+    let mut sink = Encode::<_, f32>::new(VecSink::new(), Endianness::Big);
+    sink.blocking_grant(2).unwrap();
+    sink.view_mut()[..2].copy_from_slice(&[1.5, -2.]);
+    sink.release(2);
+    sink.blocking_grant(1).unwrap();
+    sink.view_mut()[0] = 3.25;
+    sink.release(1);
+
+    let expected = [
+        1.5f32.to_be_bytes(),
+        (-2f32).to_be_bytes(),
+        3.25f32.to_be_bytes(),
+    ]
+    .concat();
+    assert_eq!(sink.into_inner().into_inner(), expected);
+}
+
+#[tokio::test]
+async fn round_trip() {
+    let (sink, source) = circular_buffer::<u8>(4096);
+    let mut sink = Encode::<_, [i32; 2]>::new(sink, Endianness::Little);
+    let mut source = Decode::<_, [i32; 2]>::new(source.into_view(), Endianness::Little);
+
+    // Writes and reads wrap around the buffer many times.
+    let write = tokio::spawn(async move {
+        for i in 0..1000 {
+            sink.grant(100).await.unwrap();
+            for (j, value) in sink.view_mut()[..100].iter_mut().enumerate() {
+                let x = i * 100 + j as i32;
+                *value = [x, -x];
+            }
+            sink.release(100);
+        }
+    });
+    let read = tokio::spawn(async move {
+        let mut count = 0;
+        loop {
+            source.grant(77).await.unwrap();
+            let view = source.view();
+            if view.is_empty() {
+                break count;
+            }
+            for value in view {
+                assert_eq!(*value, [count, -count]);
+                count += 1;
+            }
+            let len = view.len();
+            source.release(len);
+        }
+    });
+
+    let (write, read) = tokio::join!(write, read);
+    write.unwrap();
+    assert_eq!(read.unwrap(), 100 * 1000);
+}
@@ -0,0 +1,56 @@
+use rivulet::{
+    circular_buffer, error::GrantOverflow, framing, slice::Slice, Error, SplittableView, View,
+};
+
+#[test]
+fn err_into() {
+    let storage: Vec<u8> = (0..100).collect();
+    let mut slice = Slice::new(&storage).into_view().err_into::<Error>();
+    slice.blocking_grant(1).unwrap();
+
+    let (_sink, source) = circular_buffer::<u8>(4096);
+    let mut source = source.into_view().err_into::<Error>();
+    assert!(matches!(
+        source.blocking_grant(usize::MAX),
+        Err(Error::GrantOverflow(GrantOverflow(_)))
+    ));
+
+    let (mut sink, source) = circular_buffer::lossy_circular_buffer::<u8>(4096);
+    let mut source = source.err_into::<Error>();
+    let capacity = sink.try_grant(usize::MAX).unwrap_err().0;
+    for _ in 0..2 {
+        sink.blocking_grant(capacity).unwrap();
+        sink.release(capacity);
+    }
+    assert!(matches!(
+        source.blocking_grant(1),
+        Err(Error::ReaderEvicted)
+    ));
+}
+
+#[test]
+fn io_error() {
+    let e: std::io::Error = Error::GrantOverflow(GrantOverflow(10)).into();
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput);
+
+    let e: std::io::Error = Error::Timeout.into();
+    assert_eq!(e.kind(), std::io::ErrorKind::TimedOut);
+
+    let e: Error = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+    let e: std::io::Error = e.into();
+    assert_eq!(e.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn framing_error() {
+    let e: Error = framing::Error::View(GrantOverflow(10)).into();
+    assert!(matches!(e, Error::GrantOverflow(GrantOverflow(10))));
+
+    let e: Error = framing::Error::<GrantOverflow>::TooLong(5).into();
+    assert!(matches!(e, Error::TooLong(5)));
+
+    let e: Error = framing::Error::<GrantOverflow>::Truncated.into();
+    assert!(matches!(e, Error::Truncated));
+    let e: std::io::Error = e.into();
+    assert_eq!(e.kind(), std::io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,68 @@
+use rivulet::{
+    file::{File, FileMut},
+    SplittableView, View, ViewMut,
+};
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("rivulet-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn file() {
+    let path = temp_path("file");
+    let storage: Vec<u8> = (0..100).collect();
+    std::fs::write(&path, &storage).unwrap();
+
+    let mut stream = File::open(&path).unwrap().into_view();
+    std::fs::remove_file(&path).unwrap();
+
+    // There should be an immediate view of the entire file
+    stream.blocking_grant(10).unwrap();
+    assert_eq!(stream.view(), storage);
+
+    // Release a few items
+    stream.release(5);
+    assert_eq!(stream.view(), &storage[5..]);
+
+    // Release the rest
+    stream.release(95);
+    stream.blocking_grant(1).unwrap();
+    assert!(stream.view().is_empty());
+}
+
+#[test]
+fn file_empty() {
+    let path = temp_path("file_empty");
+    std::fs::write(&path, []).unwrap();
+
+    let mut stream = File::open(&path).unwrap().into_view();
+    std::fs::remove_file(&path).unwrap();
+
+    stream.blocking_grant(1).unwrap();
+    assert!(stream.view().is_empty());
+}
+
+#[test]
+fn file_mut() {
+    let path = temp_path("file_mut");
+
+    let file = FileMut::create(&path, 100).unwrap();
+    assert_eq!(file.len(), 100);
+    let (first, second) = file.sequence();
+    let (mut first, mut second) = (first.into_view(), second.into_view());
+
+    first.blocking_grant(100).unwrap();
+    for (i, value) in first.view_mut().iter_mut().enumerate() {
+        *value = i as u8;
+    }
+    first.release(100);
+    drop(first);
+
+    second.blocking_grant(100).unwrap();
+    let expected: Vec<u8> = (0..100).collect();
+    assert_eq!(second.view(), expected);
+    drop(second);
+
+    assert_eq!(std::fs::read(&path).unwrap(), expected);
+    std::fs::remove_file(&path).unwrap();
+}
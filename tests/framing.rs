@@ -0,0 +1,194 @@
+use rivulet::{
+    circular_buffer,
+    endian::Endianness,
+    framing::{Config, DelimitedReader, Error, FrameReader, FrameWriter, Prefix},
+    slice::{Slice, VecSink},
+    SplittableView, View, ViewMut,
+};
+
+fn frames() -> Vec<Vec<u8>> {
+    (0..100usize)
+        .map(|i| (0..i * 97).map(|j| (i + j) as u8).collect())
+        .collect()
+}
+
+#[test]
+fn fixed_prefix() {
+    let bytes = [0, 3, 1, 2, 3, 0, 0, 0, 2, 4];
+    let mut reader = FrameReader::new(Slice::new(&bytes).into_view(), Config::new(Prefix::U16));
+    assert_eq!(reader.blocking_next().unwrap(), Some([1, 2, 3].as_ref()));
+    assert_eq!(reader.blocking_next().unwrap(), Some([].as_ref()));
+    assert!(matches!(reader.blocking_next(), Err(Error::Truncated)));
+
+    let bytes = [2, 0, 0, 0, 5, 6];
+    let config = Config::new(Prefix::U32).endianness(Endianness::Little);
+    let mut reader = FrameReader::new(Slice::new(&bytes).into_view(), config);
+    assert_eq!(reader.blocking_next().unwrap(), Some([5, 6].as_ref()));
+    assert_eq!(reader.blocking_next().unwrap(), None);
+}
+
+#[test]
+fn varint_prefix() {
+    let mut writer = FrameWriter::new(VecSink::new(), Config::new(Prefix::Varint));
+    writer.blocking_write(&[7; 200]).unwrap();
+
+    // The prefix is padded when the frame is shorter than reserved.
+    writer.blocking_reserve(200).unwrap()[..2].copy_from_slice(&[8, 9]);
+    writer.commit(2);
+
+    let bytes = writer.into_inner().into_inner();
+    assert_eq!(&bytes[..2], [0xc8, 0x01]);
+    assert_eq!(&bytes[202..206], [0x82, 0x00, 8, 9]);
+
+    let mut reader = FrameReader::new(Slice::new(&bytes).into_view(), Config::new(Prefix::Varint));
+    assert_eq!(reader.blocking_next().unwrap(), Some([7; 200].as_ref()));
+    assert_eq!(reader.blocking_next().unwrap(), Some([8, 9].as_ref()));
+    assert_eq!(reader.blocking_next().unwrap(), None);
+}
+
+#[test]
+fn max_frame_len() {
+    let config = Config::new(Prefix::U16).max_frame_len(4);
+    let mut writer = FrameWriter::new(VecSink::new(), config);
+    writer.blocking_write(&[1; 4]).unwrap();
+    assert!(matches!(
+        writer.blocking_write(&[1; 5]),
+        Err(Error::TooLong(5))
+    ));
+
+    let bytes = [0, 4, 1, 1, 1, 1, 0, 5, 1, 1, 1, 1, 1];
+    let mut reader = FrameReader::new(Slice::new(&bytes).into_view(), config);
+    assert_eq!(reader.blocking_next().unwrap(), Some([1; 4].as_ref()));
+    assert!(matches!(reader.blocking_next(), Err(Error::TooLong(5))));
+}
+
+#[tokio::test]
+async fn circular_buffer_frames() {
+    let (mut sink, source) = circular_buffer::<u8>(1024);
+
+    // Frames larger than the buffer are copied in pieces.
+    let capacity = sink.try_grant(usize::MAX).unwrap_err().0;
+    let config = Config::new(Prefix::Varint).max_grant(capacity);
+
+    let write = tokio::spawn(async move {
+        let mut writer = FrameWriter::new(sink, config);
+        for frame in frames() {
+            writer.write(&frame).await.unwrap();
+        }
+    });
+    let read = tokio::spawn(async move {
+        let mut reader = FrameReader::new(source.into_view(), config);
+        let mut count = 0;
+        while let Some(frame) = reader.next().await.unwrap() {
+            assert_eq!(frame, frames()[count]);
+            count += 1;
+        }
+        count
+    });
+
+    let (write, read) = tokio::join!(write, read);
+    write.unwrap();
+    assert_eq!(read.unwrap(), 100);
+}
+
+#[tokio::test]
+async fn circular_buffer_frames_default_max_grant() {
+    let (mut sink, source) = circular_buffer::<u8>(1024);
+
+    // The maximum grant is detected when a frame is larger than the buffer.
+    let config = Config::new(Prefix::U32);
+    let capacity = sink.try_grant(usize::MAX).unwrap_err().0;
+    assert!(frames().iter().any(|frame| frame.len() > capacity));
+
+    let write = tokio::spawn(async move {
+        let mut writer = FrameWriter::new(sink, config);
+        for frame in frames() {
+            writer.write(&frame).await.unwrap();
+        }
+    });
+    let read = tokio::spawn(async move {
+        let mut reader = FrameReader::new(source.into_view(), config);
+        let mut count = 0;
+        while let Some(frame) = reader.next().await.unwrap() {
+            assert_eq!(frame, frames()[count]);
+            count += 1;
+        }
+        count
+    });
+
+    let (write, read) = tokio::join!(write, read);
+    write.unwrap();
+    assert_eq!(read.unwrap(), 100);
+}
+
+#[test]
+fn delimited() {
+    let bytes = b"first\n\nthird\nlast";
+    let mut reader = DelimitedReader::new(Slice::new(bytes).into_view(), b'\n', 16);
+    assert_eq!(reader.blocking_next().unwrap(), Some(b"first".as_ref()));
+    assert_eq!(reader.blocking_next().unwrap(), Some(b"".as_ref()));
+    assert_eq!(reader.blocking_next().unwrap(), Some(b"third".as_ref()));
+    assert_eq!(reader.blocking_next().unwrap(), Some(b"last".as_ref()));
+    assert_eq!(reader.blocking_next().unwrap(), None);
+
+    let mut reader = DelimitedReader::new(Slice::new(bytes).into_view(), b'\n', 4);
+    assert!(matches!(reader.blocking_next(), Err(Error::TooLong(5))));
+}
+
+#[tokio::test]
+async fn delimited_circular_buffer() {
+    let (mut sink, source) = circular_buffer::<u8>(1024);
+    let capacity = sink.try_grant(usize::MAX).unwrap_err().0;
+
+    // Records are written in small pieces, so the reader must wait for the delimiter.
+    let write = tokio::spawn(async move {
+        let records = (0..1000)
+            .map(|i| format!("record {}\n", i))
+            .collect::<String>();
+        for chunk in records.as_bytes().chunks(7) {
+            sink.grant(chunk.len()).await.unwrap();
+            sink.view_mut()[..chunk.len()].copy_from_slice(chunk);
+            sink.release(chunk.len());
+        }
+    });
+    let read = tokio::spawn(async move {
+        let mut reader = DelimitedReader::new(source.into_view(), b'\n', capacity - 1);
+        let mut count = 0;
+        while let Some(record) = reader.next().await.unwrap() {
+            assert_eq!(record, format!("record {}", count).as_bytes());
+            count += 1;
+        }
+        count
+    });
+
+    let (write, read) = tokio::join!(write, read);
+    write.unwrap();
+    assert_eq!(read.unwrap(), 1000);
+}
+
+#[test]
+fn delimited_larger_than_buffer() {
+    let (mut sink, source) = circular_buffer::<u8>(1024);
+    let capacity = sink.try_grant(usize::MAX).unwrap_err().0;
+
+    let write = std::thread::spawn(move || {
+        let records = [b"short\n".to_vec(), vec![b'x'; capacity * 2]].concat();
+        // Write one byte at a time, so the reader can fill the entire buffer.
+        for chunk in records.chunks(1) {
+            sink.blocking_grant(chunk.len()).unwrap();
+            if sink.view().len() < chunk.len() {
+                // The reader was dropped
+                break;
+            }
+            sink.view_mut()[..chunk.len()].copy_from_slice(chunk);
+            sink.release(chunk.len());
+        }
+    });
+
+    // Records that don't fit in the buffer are too long, even if they're shorter than `max_len`.
+    let mut reader = DelimitedReader::new(source.into_view(), b'\n', capacity * 4);
+    assert_eq!(reader.blocking_next().unwrap(), Some(b"short".as_ref()));
+    assert!(matches!(reader.blocking_next(), Err(Error::TooLong(len)) if len as usize == capacity));
+    drop(reader);
+    write.join().unwrap();
+}
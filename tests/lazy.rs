@@ -63,3 +63,106 @@ async fn lazy_channel() {
     let (write_hash, read_hash) = futures::future::join(write_hash, read_hash).await;
     assert_eq!(write_hash.unwrap(), read_hash.unwrap());
 }
+
+#[tokio::test]
+async fn lazy_channel_read_before_write() {
+    let (mut sink, mut source) = lazy::lazy_channel(|| {
+        let (sink, source) = circular_buffer::<i64>(BUFFER_SIZE);
+        (sink, source.into_view())
+    });
+
+    // The channel isn't initialized, so the source should wait.
+    let waker = futures::task::noop_waker();
+    let mut cx = std::task::Context::from_waker(&waker);
+    assert!(std::pin::Pin::new(&mut source)
+        .poll_grant(&mut cx, 1)
+        .is_pending());
+
+    let read = tokio::spawn(async move {
+        source.grant(1).await.unwrap();
+        source.view()[0]
+    });
+
+    sink.grant(1).await.unwrap();
+    sink.view_mut()[0] = 123;
+    sink.release(1);
+
+    assert_eq!(read.await.unwrap(), 123);
+}
+
+#[tokio::test]
+async fn lazy_channel_drop_uninitialized() {
+    let (sink, mut source) = lazy::lazy_channel(|| {
+        let (sink, source) = circular_buffer::<i64>(BUFFER_SIZE);
+        (sink, source.into_view())
+    });
+
+    let read = tokio::spawn(async move {
+        source.grant(1).await.unwrap();
+        source.view().len()
+    });
+    std::mem::drop(sink);
+
+    // The source should reach the end of the stream.
+    assert_eq!(read.await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn lazy_channel_concurrent() {
+    for _ in 0..100 {
+        let (sink, source) = lazy::lazy_channel(|| {
+            let (sink, source) = circular_buffer::<i64>(BUFFER_SIZE);
+            (sink, source.into_view())
+        });
+
+        let read_hash = tokio::spawn(read(source));
+        let write_hash = tokio::spawn(write(sink, 500, 10));
+
+        let (write_hash, read_hash) = futures::future::join(write_hash, read_hash).await;
+        assert_eq!(write_hash.unwrap(), read_hash.unwrap());
+    }
+}
+
+#[tokio::test]
+async fn lazy_async_view() {
+    let (sink, source) = circular_buffer::<i64>(BUFFER_SIZE);
+
+    let sink = lazy::LazyAsync::new(Box::pin(async move {
+        tokio::task::yield_now().await;
+        Ok::<_, rivulet::error::GrantOverflow>(sink)
+    }));
+    let source = lazy::LazyAsync::new(Box::pin(async move {
+        tokio::task::yield_now().await;
+        Ok::<_, rivulet::error::GrantOverflow>(source.into_view())
+    }));
+
+    let write_hash = tokio::spawn(write(sink, 500, 400));
+    let read_hash = tokio::spawn(read(source));
+
+    let (write_hash, read_hash) = futures::future::join(write_hash, read_hash).await;
+    assert_eq!(write_hash.unwrap(), read_hash.unwrap());
+}
+
+#[tokio::test]
+async fn lazy_async_error() {
+    #[derive(Debug, PartialEq)]
+    enum Error {
+        Init,
+        Grant,
+    }
+
+    impl From<rivulet::error::GrantOverflow> for Error {
+        fn from(_: rivulet::error::GrantOverflow) -> Self {
+            Self::Grant
+        }
+    }
+
+    let mut source = lazy::LazyAsync::new(Box::pin(async {
+        Err::<rivulet::splittable::View<rivulet::circular_buffer::Source<i64>>, _>(Error::Init)
+    }));
+    assert_eq!(source.grant(1).await.unwrap_err(), Error::Init);
+
+    // The view is closed after an error
+    source.grant(1).await.unwrap();
+    assert!(source.view().is_empty());
+}
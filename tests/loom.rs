@@ -0,0 +1,198 @@
+//! Model checks of the lock-free stream protocols.
+//!
+//! Run with `RUSTFLAGS="--cfg loom" cargo test --release --test loom`.
+#![cfg(loom)]
+
+use loom::{future::block_on, thread};
+use rivulet::{
+    circular_buffer,
+    circular_buffer::{lossy_circular_buffer, LossyError},
+    SplittableView, View, ViewMut,
+};
+
+/// An element large enough that a circular buffer only holds a few of them.
+#[derive(Copy, Clone)]
+struct Page(u64, #[allow(dead_code)] [u8; 2040]);
+
+impl Default for Page {
+    fn default() -> Self {
+        Self(0, [0; 2040])
+    }
+}
+
+/// The number of elements written by each model, more than the buffer capacity.
+const COUNT: u64 = 3;
+
+fn model(f: impl Fn() + Sync + Send + 'static) {
+    model_with_preemptions(2, f)
+}
+
+/// Check a model with a default preemption bound, which may be overridden by
+/// `LOOM_MAX_PREEMPTIONS`.
+fn model_with_preemptions(preemptions: usize, f: impl Fn() + Sync + Send + 'static) {
+    let mut builder = loom::model::Builder::new();
+    if builder.preemption_bound.is_none() {
+        builder.preemption_bound = Some(preemptions);
+    }
+    builder.check(f);
+}
+
+fn write(mut sink: impl ViewMut<Item = Page>) {
+    for i in 0..COUNT {
+        block_on(sink.grant(1)).unwrap();
+        if sink.view().is_empty() {
+            // The readers were dropped
+            break;
+        }
+        sink.view_mut()[0].0 = i;
+        sink.release(1);
+    }
+}
+
+fn read(mut source: impl View<Item = Page>) -> u64 {
+    let mut count = 0;
+    loop {
+        block_on(source.grant(1)).unwrap();
+        let view = source.view();
+        if view.is_empty() {
+            break count;
+        }
+        assert_eq!(view[0].0, count);
+        count += 1;
+        source.release(1);
+    }
+}
+
+#[test]
+fn capacity() {
+    model(|| {
+        let (mut sink, _source) = circular_buffer::<Page>(1);
+        assert!(sink.try_grant(usize::MAX).unwrap_err().0 < COUNT as usize);
+    });
+}
+
+#[test]
+fn writer_reader_wakeup() {
+    model(|| {
+        let (sink, source) = circular_buffer::<Page>(1);
+        let writer = thread::spawn(move || write(sink));
+        assert_eq!(read(source.into_view()), COUNT);
+        writer.join().unwrap();
+    });
+}
+
+#[test]
+fn reader_drop_wakes_writer() {
+    model(|| {
+        let (sink, source) = circular_buffer::<Page>(1);
+        let writer = thread::spawn(move || write(sink));
+        drop(source);
+        writer.join().unwrap();
+    });
+}
+
+#[test]
+fn cloneable_drop_race() {
+    model(|| {
+        let (sink, source) = circular_buffer::<Page>(1);
+        let source = source.into_cloneable_view();
+        let clone = source.clone();
+
+        // One reader is dropped while the other reads, and the writer must not stall.
+        let writer = thread::spawn(move || write(sink));
+        let dropper = thread::spawn(move || drop(clone));
+        assert_eq!(read(source), COUNT);
+        dropper.join().unwrap();
+        writer.join().unwrap();
+    });
+}
+
+#[test]
+fn cloneable_max_readers_drop_race() {
+    model(|| {
+        let (sink, source) = circular_buffer::<Page>(1);
+        let source = source.into_cloneable_view_with_max_readers(2);
+        let clone = source.clone();
+
+        let writer = thread::spawn(move || write(sink));
+        let dropper = thread::spawn(move || drop(clone));
+        assert_eq!(read(source), COUNT);
+        dropper.join().unwrap();
+        writer.join().unwrap();
+    });
+}
+
+#[test]
+fn cloneable_max_readers_clone_race() {
+    model(|| {
+        let (sink, source) = circular_buffer::<Page>(1);
+        let source = source.into_cloneable_view_with_max_readers(2);
+
+        // A reader is cloned while the writer wakes the readers.
+        let writer = thread::spawn(move || write(sink));
+        let clone = source.clone();
+        drop(source);
+        assert_eq!(read(clone), COUNT);
+        writer.join().unwrap();
+    });
+}
+
+#[test]
+fn lossy_overrun() {
+    model(|| {
+        let (sink, mut source) = lossy_circular_buffer::<Page>(1);
+        let writer = thread::spawn(move || write(sink));
+
+        // Every element is either received in order or reported lost.
+        let mut expected = 0;
+        loop {
+            match block_on(source.grant(1)) {
+                Ok(()) => {}
+                Err(LossyError::Overrun(lost)) => {
+                    expected += lost;
+                    continue;
+                }
+                Err(e) => panic!("{}", e),
+            }
+            let view = source.view();
+            if view.is_empty() {
+                break;
+            }
+            assert_eq!(view[0].0, expected);
+            expected += 1;
+            source.release(1);
+        }
+        assert_eq!(expected, COUNT);
+        writer.join().unwrap();
+    });
+}
+
+#[test]
+fn sequence_second_drop() {
+    model(|| {
+        let (sink, source) = circular_buffer::<Page>(1);
+        let (first, second) = source.sequence();
+
+        // `Second` is dropped while `First` sets the head, and the writer must not stall.
+        let writer = thread::spawn(move || write(sink));
+        let dropper = thread::spawn(move || drop(second));
+        assert_eq!(read(first.into_view()), COUNT);
+        dropper.join().unwrap();
+        writer.join().unwrap();
+    });
+}
+
+#[test]
+fn sequence_wakeup() {
+    // With four threads, a larger bound takes too long to check.
+    model_with_preemptions(1, || {
+        let (sink, source) = circular_buffer::<Page>(1);
+        let (first, second) = source.sequence();
+
+        let writer = thread::spawn(move || write(sink));
+        let first = thread::spawn(move || read(first.into_view()));
+        assert_eq!(read(second.into_view()), COUNT);
+        assert_eq!(first.join().unwrap(), COUNT);
+        writer.join().unwrap();
+    });
+}
@@ -0,0 +1,86 @@
+use rivulet::{circular_buffer, SplittableView, View, ViewMut};
+
+fn fill(sink: &mut impl ViewMut<Item = u32>, values: std::ops::Range<u32>) {
+    let len = values.len();
+    sink.blocking_grant(len).unwrap();
+    for (value, i) in sink.view_mut().iter_mut().zip(values) {
+        *value = i;
+    }
+    sink.release(len);
+}
+
+#[test]
+fn mark_rewind() {
+    let (mut sink, source) = circular_buffer::<u32>(4096);
+    let mut source = source.into_view();
+    fill(&mut sink, 0..10);
+
+    source.blocking_grant(10).unwrap();
+    source.release(2);
+    source.mark();
+
+    // Read past the mark
+    source.release(5);
+    assert_eq!(source.view(), &[7, 8, 9]);
+
+    // Return to the mark
+    source.rewind();
+    assert_eq!(source.view(), &[2, 3, 4, 5, 6, 7, 8, 9]);
+
+    // Grants include new data after rewinding
+    source.release(8);
+    fill(&mut sink, 10..12);
+    source.blocking_grant(2).unwrap();
+    source.rewind();
+    assert_eq!(source.view(), &[2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+}
+
+#[test]
+fn mark_retains_data() {
+    let (mut sink, source) = circular_buffer::<u32>(4096);
+    let capacity = sink.try_grant(usize::MAX).unwrap_err().0;
+    let mut source = source.into_view();
+
+    source.mark();
+    fill(&mut sink, 0..100);
+    source.blocking_grant(100).unwrap();
+    source.release(100);
+
+    // The released data is retained, so the sink can't reuse it
+    assert!(!sink.try_grant(capacity - 99).unwrap());
+    // Grants count the retained data
+    assert!(source.blocking_grant(capacity - 99).is_err());
+
+    // Removing the mark frees the data
+    source.unmark();
+    assert!(sink.try_grant(capacity).unwrap());
+}
+
+#[test]
+fn mark_rewind_cloneable() {
+    let (mut sink, source) = circular_buffer::<u32>(4096);
+    let mut first = source.into_cloneable_view();
+    fill(&mut sink, 0..10);
+
+    first.blocking_grant(10).unwrap();
+    first.mark();
+    first.release(4);
+
+    // Clones share the mark
+    let mut second = first.clone();
+    first.release(6);
+    first.rewind();
+    assert_eq!(first.view(), &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    second.rewind();
+    assert_eq!(second.view(), &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    // The data is retained until both marks are removed
+    first.unmark();
+    first.release(10);
+    fill(&mut sink, 10..20);
+    second.release(10);
+    second.blocking_grant(10).unwrap();
+    second.rewind();
+    assert_eq!(second.view().len(), 20);
+    second.unmark();
+}
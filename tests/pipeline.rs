@@ -0,0 +1,181 @@
+use rivulet::{
+    pipeline::{Failure, Pipeline},
+    process, Error, SplittableView, View, ViewMut,
+};
+
+async fn generate(
+    mut sink: impl ViewMut<Item = u64, Error = rivulet::error::GrantOverflow>,
+    count: u64,
+) -> Result<(), Error> {
+    for i in 0..count {
+        sink.grant(1).await?;
+        sink.view_mut()[0] = i;
+        sink.release(1);
+    }
+    Ok(())
+}
+
+fn double(input: &[u64], output: &mut [u64]) {
+    for (x, y) in input.iter().zip(output) {
+        *y = x * 2;
+    }
+}
+
+#[tokio::test]
+async fn pipeline_run() {
+    let mut pipeline = Pipeline::<Error>::new();
+    let numbers = pipeline.source("generate", 4096, |sink| generate(sink, 100_000));
+    let doubled = pipeline.transform("double", numbers, 4096, |source, sink| {
+        process::copy_with(source.into_view(), sink, double)
+    });
+    let (count_sender, count_receiver) = tokio::sync::oneshot::channel();
+    pipeline.sink("count", doubled, |source| async move {
+        let mut source = source.into_view();
+        let mut count = 0;
+        loop {
+            source.grant(1).await?;
+            let view = source.view();
+            if view.is_empty() {
+                break;
+            }
+            for x in view {
+                assert_eq!(*x, count * 2);
+                count += 1;
+            }
+            let len = view.len();
+            source.release(len);
+        }
+        count_sender.send(count).unwrap();
+        Ok::<_, Error>(())
+    });
+    assert_eq!(pipeline.len(), 3);
+
+    pipeline.run().await.unwrap();
+    assert_eq!(count_receiver.await.unwrap(), 100_000);
+}
+
+#[tokio::test]
+async fn pipeline_run_error() {
+    let mut pipeline = Pipeline::<Error>::new();
+    let numbers = pipeline.source("generate", 4096, |sink| generate(sink, 100));
+    pipeline.sink("fail", numbers, |_| async { Err::<(), _>(Error::Aborted) });
+
+    let error = pipeline.run().await.unwrap_err();
+    assert_eq!(error.stage(), "fail");
+    assert!(matches!(error.into_inner(), Failure::Error(Error::Aborted)));
+}
+
+#[tokio::test]
+async fn pipeline_spawn_cancel() {
+    let mut pipeline = Pipeline::<Error>::new();
+
+    // This stage never completes, and must be aborted when the other stage fails.
+    let (sink, source) = pipeline.channel::<u64>(4096);
+    pipeline.stage("stall", async move {
+        let _sink = sink;
+        futures::future::pending::<()>().await;
+        Ok::<_, Error>(())
+    });
+    pipeline.stage("fail", async { Err::<(), _>(Error::Timeout) });
+
+    let error = pipeline
+        .spawn(|stage| {
+            tokio::spawn(stage);
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.stage(), "fail");
+    assert!(matches!(error.error(), Some(Error::Timeout)));
+
+    // The stalled stage was dropped, closing its sink.
+    let mut source = source.into_view();
+    source.grant(1).await.unwrap();
+    assert!(source.view().is_empty());
+}
+
+#[tokio::test]
+async fn pipeline_spawn_panic() {
+    let mut pipeline = Pipeline::<Error>::new();
+
+    // This stage never completes, and must be aborted when the other stage panics.
+    let (sink, source) = pipeline.channel::<u64>(4096);
+    pipeline.stage("stall", async move {
+        let _sink = sink;
+        futures::future::pending::<()>().await;
+        Ok::<_, Error>(())
+    });
+    pipeline.stage("panic", async {
+        panic!("stage panicked");
+        #[allow(unreachable_code)]
+        Ok::<_, Error>(())
+    });
+
+    let error = pipeline
+        .spawn(|stage| {
+            tokio::spawn(stage);
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.stage(), "panic");
+    assert!(matches!(error.failure(), Failure::Panicked));
+
+    let mut source = source.into_view();
+    source.grant(1).await.unwrap();
+    assert!(source.view().is_empty());
+}
+
+#[tokio::test]
+async fn pipeline_spawn_dropped() {
+    let mut pipeline = Pipeline::<Error>::new();
+    pipeline.stage("dropped", futures::future::pending::<Result<(), Error>>());
+
+    // The executor drops the stage without completing it.
+    let error = pipeline.spawn(drop).await.unwrap_err();
+    assert_eq!(error.stage(), "dropped");
+    assert!(matches!(error.failure(), Failure::Cancelled));
+}
+
+#[tokio::test]
+async fn pipeline_spawn() {
+    let mut pipeline = Pipeline::<Error>::new();
+    let numbers = pipeline.source("generate", 4096, |sink| generate(sink, 100_000));
+    let doubled = pipeline.transform("double", numbers, 4096, |source, sink| {
+        process::copy_with(source.into_view(), sink, double)
+    });
+
+    // Read the last connection outside of the pipeline.
+    let mut output = doubled.into_inner().into_view();
+    let run = tokio::spawn(pipeline.spawn(|stage| {
+        tokio::spawn(stage);
+    }));
+    let mut count = 0;
+    loop {
+        output.grant(1).await.unwrap();
+        let view = output.view();
+        if view.is_empty() {
+            break;
+        }
+        count += view.len() as u64;
+        let len = view.len();
+        output.release(len);
+    }
+    run.await.unwrap().unwrap();
+    assert_eq!(count, 100_000);
+}
+
+#[test]
+fn pipeline_blocking_run() {
+    let mut pipeline = Pipeline::<Error>::new();
+    let numbers = pipeline.source("generate", 4096, |sink| generate(sink, 100_000));
+    let doubled = pipeline.transform("double", numbers, 4096, |source, sink| {
+        process::copy_with(source.into_view(), sink, double)
+    });
+    pipeline.sink("fail", doubled, |source| async move {
+        let mut source = source.into_view();
+        source.grant(1000).await?;
+        Err::<(), _>(Error::Aborted)
+    });
+
+    let error = pipeline.blocking_run().unwrap_err();
+    assert_eq!(error.stage(), "fail");
+}
@@ -0,0 +1,179 @@
+use rivulet::{
+    circular_buffer,
+    process::{blocking_copy, blocking_copy_with, blocking_process, copy, process, Error},
+    slice::{Slice, SliceMut, VecSink},
+    SplittableView, View, ViewMut,
+};
+
+#[test]
+fn copy_slice() {
+    let input: Vec<u32> = (0..1000).collect();
+    let mut sink = VecSink::new();
+    let copied = blocking_copy(Slice::new(&input).into_view(), &mut sink).unwrap();
+    assert_eq!(copied, 1000);
+    assert_eq!(sink.into_inner(), input);
+}
+
+#[test]
+fn copy_with_short_sink() {
+    // Copying stops at the end of the sink.
+    let input: Vec<u32> = (0..1000).collect();
+    let mut output = vec![0.; 100];
+    let copied = blocking_copy_with(
+        Slice::new(&input).into_view(),
+        SliceMut::new(&mut output).into_view(),
+        |input, output| {
+            for (x, y) in input.iter().zip(output) {
+                *y = *x as f32 * 2.;
+            }
+        },
+    )
+    .unwrap();
+    assert_eq!(copied, 100);
+    assert_eq!(output, (0..100).map(|x| x as f32 * 2.).collect::<Vec<_>>());
+}
+
+#[test]
+fn copy_error() {
+    let input = [0u8; 10];
+    let result = blocking_copy(Slice::new(&input).into_view(), VecSink::with_max_len(5));
+    assert!(matches!(result, Err(Error::Sink(_))));
+}
+
+#[test]
+fn copy_error_while_waiting() {
+    // The source fails immediately, while the sink waits for space forever.
+    let (mut lossy_sink, lossy_source) = circular_buffer::lossy_circular_buffer::<u8>(4096);
+    let capacity = lossy_sink.try_grant(usize::MAX).unwrap_err().0;
+    for _ in 0..2 {
+        lossy_sink.blocking_grant(capacity).unwrap();
+        lossy_sink.release(capacity);
+    }
+
+    let (mut sink, _source) = circular_buffer::<u8>(4096);
+    sink.blocking_grant(capacity).unwrap();
+    sink.release(capacity);
+
+    let result = blocking_copy(lossy_source, sink);
+    assert!(matches!(
+        result,
+        Err(Error::Source(circular_buffer::LossyError::Overrun(_)))
+    ));
+}
+
+#[tokio::test]
+async fn copy_circular_buffers() {
+    let (mut sink, source) = circular_buffer::<u64>(4096);
+    let (forward_sink, forward_source) = circular_buffer::<u64>(4096);
+
+    let write = tokio::spawn(async move {
+        for i in 0..1000 {
+            sink.grant(100).await.unwrap();
+            for (j, value) in sink.view_mut()[..100].iter_mut().enumerate() {
+                *value = i * 100 + j as u64;
+            }
+            sink.release(100);
+        }
+    });
+    let forward = tokio::spawn(copy(source.into_view(), forward_sink));
+    let read = tokio::spawn(async move {
+        let mut source = forward_source.into_view();
+        let mut count = 0;
+        loop {
+            source.grant(1).await.unwrap();
+            let view = source.view();
+            if view.is_empty() {
+                break count;
+            }
+            for value in view {
+                assert_eq!(*value, count);
+                count += 1;
+            }
+            let len = view.len();
+            source.release(len);
+        }
+    });
+
+    let (write, forward, read) = tokio::join!(write, forward, read);
+    write.unwrap();
+    assert_eq!(forward.unwrap().unwrap(), 100 * 1000);
+    assert_eq!(read.unwrap(), 100 * 1000);
+}
+
+#[test]
+fn process_interpolate() {
+    // Every 3 inputs produce 2 outputs, and the 2 remaining inputs are averaged.
+    let input: Vec<f32> = (0..101).map(|x| x as f32).collect();
+    let mut sink = VecSink::new();
+    let blocks = blocking_process(
+        Slice::new(&input).into_view(),
+        &mut sink,
+        3,
+        2,
+        |input, output| {
+            output[0] = input[0];
+            output[1] = (input[1] + input[2]) / 2.;
+        },
+        |input, output| {
+            output[0] = input.iter().sum::<f32>() / input.len() as f32;
+            1
+        },
+    )
+    .unwrap();
+    assert_eq!(blocks, 33);
+
+    let output = sink.into_inner();
+    assert_eq!(output.len(), 67);
+    for (i, block) in output[..66].chunks(2).enumerate() {
+        let x = 3. * i as f32;
+        assert_eq!(block, [x, x + 1.5]);
+    }
+    assert_eq!(output[66], 99.5);
+}
+
+#[tokio::test]
+async fn process_circular_buffers() {
+    let (mut sink, source) = circular_buffer::<u32>(4096);
+    let (process_sink, process_source) = circular_buffer::<u32>(4096);
+
+    let write = tokio::spawn(async move {
+        for i in 0..1000 {
+            sink.grant(77).await.unwrap();
+            for (j, value) in sink.view_mut()[..77].iter_mut().enumerate() {
+                *value = i * 77 + j as u32;
+            }
+            sink.release(77);
+        }
+    });
+    // Decimate by 7, with no tail since the input is a multiple of the block size.
+    let process = tokio::spawn(process(
+        source.into_view(),
+        process_sink,
+        7,
+        1,
+        |input, output| output[0] = input[0],
+        |_, _| unreachable!(),
+    ));
+    let read = tokio::spawn(async move {
+        let mut source = process_source.into_view();
+        let mut count = 0;
+        loop {
+            source.grant(1).await.unwrap();
+            let view = source.view();
+            if view.is_empty() {
+                break count;
+            }
+            for value in view {
+                assert_eq!(*value, count * 7);
+                count += 1;
+            }
+            let len = view.len();
+            source.release(len);
+        }
+    });
+
+    let (write, process, read) = tokio::join!(write, process, read);
+    write.unwrap();
+    assert_eq!(process.unwrap().unwrap(), 11 * 1000);
+    assert_eq!(read.unwrap(), 11 * 1000);
+}
@@ -0,0 +1,136 @@
+//! Tests that writing to a circular buffer doesn't allocate in steady state.
+
+use rivulet::{circular_buffer, SplittableView, View, ViewMut};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+    sync::Arc,
+};
+
+/// Counts allocations made by threads that enable tracking.
+struct CountingAllocator;
+
+// `const` thread locals aren't supported by the minimum supported Rust version
+thread_local! {
+    #[allow(clippy::missing_const_for_thread_local)]
+    static TRACKING: Cell<bool> = Cell::new(false);
+    #[allow(clippy::missing_const_for_thread_local)]
+    static ALLOCATIONS: Cell<usize> = Cell::new(0);
+}
+
+fn count_allocation() {
+    let _ = TRACKING.try_with(|tracking| {
+        if tracking.get() {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        }
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Run `f` and return the number of allocations it made on this thread.
+fn allocations(f: impl FnOnce()) -> usize {
+    ALLOCATIONS.with(|count| count.set(0));
+    TRACKING.with(|tracking| tracking.set(true));
+    f();
+    TRACKING.with(|tracking| tracking.set(false));
+    ALLOCATIONS.with(|count| count.get())
+}
+
+/// Write blocks to the sink without blocking, the way a real-time thread would.
+fn write(sink: &mut impl ViewMut<Item = u64>, start: u64, blocks: u64) {
+    for i in start..start + blocks {
+        while !sink.try_grant(64).unwrap() {
+            std::thread::yield_now();
+        }
+        for (j, value) in sink.view_mut()[..64].iter_mut().enumerate() {
+            *value = i * 64 + j as u64;
+        }
+        sink.release(64);
+    }
+}
+
+/// Read every value, checking the order, and return the count.
+fn read(mut source: impl View<Item = u64>, done: Arc<AtomicBool>) -> u64 {
+    let mut count = 0;
+    loop {
+        source.blocking_grant(1).unwrap();
+        let view = source.view();
+        if view.is_empty() {
+            break;
+        }
+        for value in view {
+            assert_eq!(*value, count);
+            count += 1;
+        }
+        let len = view.len();
+        source.release(len);
+    }
+    done.store(true, Ordering::Relaxed);
+    count
+}
+
+#[test]
+fn harness_counts_allocations() {
+    assert_eq!(allocations(|| drop(Vec::<u8>::with_capacity(16))), 1);
+}
+
+#[test]
+fn view_writer_does_not_allocate() {
+    let (mut sink, source) = circular_buffer::<u64>(4096);
+    let done = Arc::new(AtomicBool::new(false));
+    let reader = {
+        let done = done.clone();
+        std::thread::spawn(move || read(source.into_view(), done))
+    };
+
+    write(&mut sink, 0, 100);
+    assert_eq!(allocations(|| write(&mut sink, 100, 10000)), 0);
+    drop(sink);
+
+    assert_eq!(reader.join().unwrap(), 10100 * 64);
+    assert!(done.load(Ordering::Relaxed));
+}
+
+#[test]
+fn cloneable_writer_does_not_allocate() {
+    let (mut sink, source) = circular_buffer::<u64>(4096);
+    let source = source.into_cloneable_view_with_max_readers(2);
+    let clone = source.clone();
+    assert!(source.try_clone().is_none());
+
+    let done = Arc::new(AtomicBool::new(false));
+    let readers = [source, clone]
+        .into_iter()
+        .map(|source| {
+            let done = done.clone();
+            std::thread::spawn(move || read(source, done))
+        })
+        .collect::<Vec<_>>();
+
+    write(&mut sink, 0, 100);
+    assert_eq!(allocations(|| write(&mut sink, 100, 10000)), 0);
+    drop(sink);
+
+    for reader in readers {
+        assert_eq!(reader.join().unwrap(), 10100 * 64);
+    }
+}
@@ -115,3 +115,49 @@ async fn sequence_drop_second() {
 
     assert_eq!(write_hash.unwrap(), read_hash.unwrap());
 }
+
+#[tokio::test]
+async fn cloneable_view_mut() {
+    use rivulet::splittable::SplittableViewMut;
+
+    let (sink, source) = circular_buffer::<i64>(BUFFER_SIZE);
+    let (view_mut, cloneable) = source.into_cloneable_view_mut();
+
+    let write_hash = tokio::spawn(write(sink, 500, 400));
+    let process = tokio::spawn(process(view_mut));
+    let read_hashes = (0..10)
+        .map(|_| tokio::spawn(read(cloneable.clone(), true)))
+        .collect::<Vec<_>>();
+    std::mem::drop(cloneable); // remaining reference doesn't get used, so drop it
+
+    let (write_hash, read_hashes, process) =
+        tokio::join!(write_hash, futures::future::join_all(read_hashes), process);
+
+    process.unwrap();
+    for read_hash in read_hashes {
+        assert_eq!(write_hash.as_ref().unwrap(), read_hash.as_ref().unwrap());
+    }
+}
+
+#[test]
+fn cloneable_max_readers() {
+    let (mut sink, source) = rivulet::circular_buffer::<u8>(4096);
+    let reader = source.into_cloneable_view_with_max_readers(2);
+
+    // Dropped readers free their slot.
+    let clone = reader.clone();
+    assert!(reader.try_clone().is_none());
+    drop(clone);
+    let mut clone = reader.try_clone().unwrap();
+
+    // The remaining readers still hold back the writer.
+    let capacity = sink.try_grant(usize::MAX).unwrap_err().0;
+    sink.blocking_grant(capacity).unwrap();
+    sink.release(capacity);
+    assert!(!sink.try_grant(1).unwrap());
+    drop(reader);
+    assert!(!sink.try_grant(1).unwrap());
+    clone.blocking_grant(capacity).unwrap();
+    clone.release(capacity);
+    assert!(sink.try_grant(1).unwrap());
+}
@@ -1,5 +1,5 @@
 use rivulet::{
-    slice::{Slice, SliceMut},
+    slice::{collect, Owned, OwnedMut, Slice, SliceMut, VecSink},
     SplittableView, View, ViewMut,
 };
 
@@ -91,3 +91,100 @@ fn bad_release() {
     let mut stream = Slice::new(&storage).into_view();
     stream.release(101);
 }
+
+#[test]
+fn owned() {
+    let storage: std::sync::Arc<[u8]> = (0..100).collect();
+    let stream = Owned::new(storage).into_cloneable_view();
+
+    let readers = (0..4)
+        .map(|_| {
+            let mut stream = stream.clone();
+            std::thread::spawn(move || {
+                let mut sum = 0u64;
+                loop {
+                    stream.blocking_grant(7).unwrap();
+                    let view = stream.view();
+                    if view.is_empty() {
+                        break sum;
+                    }
+                    let len = view.len().min(7);
+                    sum += view[..len].iter().map(|x| *x as u64).sum::<u64>();
+                    stream.release(len);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for reader in readers {
+        assert_eq!(reader.join().unwrap(), (0..100).sum());
+    }
+}
+
+#[test]
+fn owned_mut() {
+    let storage: Vec<u8> = (0..100).collect();
+    let (mut first, mut second) = {
+        let (first, second) = OwnedMut::new(storage).sequence();
+        (first.into_view(), second.into_view())
+    };
+
+    std::thread::spawn(move || {
+        first.blocking_grant(10).unwrap();
+        let view = first.view_mut();
+        assert_eq!(view.len(), 100);
+        for value in view {
+            *value *= 2;
+        }
+        first.release(100);
+    })
+    .join()
+    .unwrap();
+
+    second.blocking_grant(100).unwrap();
+    let expected: Vec<u8> = (0..100).map(|x| x * 2).collect();
+    assert_eq!(second.view(), expected);
+}
+
+#[test]
+fn vec_sink() {
+    let mut sink = VecSink::new();
+    sink.blocking_grant(10).unwrap();
+    assert_eq!(sink.view().len(), 10);
+    sink.view_mut()[..5].copy_from_slice(&[1, 2, 3, 4, 5]);
+    sink.release(5);
+    assert_eq!(sink.view().len(), 5);
+    sink.blocking_grant(20).unwrap();
+    assert_eq!(sink.view().len(), 20);
+    sink.view_mut()[0] = 6;
+    sink.release(1);
+    assert_eq!(sink.into_inner(), vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn vec_sink_max_len() {
+    let mut sink = VecSink::<u8>::with_max_len(10);
+    sink.blocking_grant(10).unwrap();
+    sink.release(4);
+    assert_eq!(sink.blocking_grant(7).unwrap_err().0, 6);
+    sink.blocking_grant(6).unwrap();
+    sink.release(6);
+    assert_eq!(sink.into_inner().len(), 10);
+}
+
+#[tokio::test]
+async fn collect_circular_buffer() {
+    let (mut sink, source) = rivulet::circular_buffer::<u32>(4096);
+
+    let writer = tokio::spawn(async move {
+        for chunk in (0..100_000).collect::<Vec<_>>().chunks(1000) {
+            sink.grant(chunk.len()).await.unwrap();
+            sink.view_mut()[..chunk.len()].copy_from_slice(chunk);
+            sink.release(chunk.len());
+        }
+    });
+
+    let collected = collect(source.into_view()).await.unwrap();
+    writer.await.unwrap();
+    assert_eq!(collected, (0..100_000).collect::<Vec<_>>());
+}
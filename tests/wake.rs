@@ -0,0 +1,131 @@
+//! Tests that readers and writers are only woken once the data or space they're waiting for is
+//! available.
+
+use futures::task::{waker, ArcWake};
+use rivulet::{circular_buffer, SplittableView, View, ViewMut};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// Counts the number of times it's woken.
+#[derive(Default)]
+struct CountingWaker(AtomicUsize);
+
+impl ArcWake for CountingWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl CountingWaker {
+    fn count(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Poll a grant once, returning the waker it registered.
+fn poll_grant(view: &mut impl View, count: usize) -> Arc<CountingWaker> {
+    let counter = Arc::new(CountingWaker::default());
+    let waker = waker(counter.clone());
+    let mut cx = Context::from_waker(&waker);
+    let mut grant = view.grant(count);
+    assert!(matches!(Pin::new(&mut grant).poll(&mut cx), Poll::Pending));
+    counter
+}
+
+fn write(sink: &mut impl ViewMut<Item = u8>, count: usize) {
+    sink.blocking_grant(count).unwrap();
+    sink.release(count);
+}
+
+#[test]
+fn cloneable_wake_threshold() {
+    let (mut sink, source) = circular_buffer::<u8>(4096);
+    let mut small = source.into_cloneable_view();
+    let mut large = small.clone();
+
+    let small_waker = poll_grant(&mut small, 1);
+    let large_waker = poll_grant(&mut large, 100);
+
+    // Small releases only wake the reader waiting for a small grant.
+    for _ in 0..10 {
+        write(&mut sink, 1);
+    }
+    assert!(small_waker.count() > 0);
+    assert_eq!(large_waker.count(), 0);
+
+    write(&mut sink, 90);
+    assert_eq!(large_waker.count(), 1);
+    large.blocking_grant(100).unwrap();
+}
+
+#[test]
+fn cloneable_max_readers_wake_threshold() {
+    let (mut sink, source) = circular_buffer::<u8>(4096);
+    let mut small = source.into_cloneable_view_with_max_readers(2);
+    let mut large = small.clone();
+
+    let small_waker = poll_grant(&mut small, 1);
+    let large_waker = poll_grant(&mut large, 100);
+
+    for _ in 0..10 {
+        write(&mut sink, 1);
+    }
+    assert!(small_waker.count() > 0);
+    assert_eq!(large_waker.count(), 0);
+
+    write(&mut sink, 90);
+    assert_eq!(large_waker.count(), 1);
+}
+
+#[test]
+fn closing_wakes_all_readers() {
+    let (mut sink, source) = circular_buffer::<u8>(4096);
+    let mut reader = source.into_cloneable_view();
+
+    let waker = poll_grant(&mut reader, 100);
+    write(&mut sink, 1);
+    assert_eq!(waker.count(), 0);
+
+    // Readers are woken to observe the end of the stream, without the data they're waiting for.
+    drop(sink);
+    assert_eq!(waker.count(), 1);
+    reader.blocking_grant(100).unwrap();
+    assert_eq!(reader.view().len(), 1);
+}
+
+#[test]
+fn writer_wake_threshold() {
+    let (mut sink, source) = circular_buffer::<u8>(4096);
+    let mut reader = source.into_view();
+    let capacity = sink.try_grant(usize::MAX).unwrap_err().0;
+    write(&mut sink, capacity);
+    reader.blocking_grant(capacity).unwrap();
+
+    // Small releases don't wake the writer waiting for a large grant.
+    let waker = poll_grant(&mut sink, 100);
+    for _ in 0..10 {
+        reader.release(1);
+    }
+    assert_eq!(waker.count(), 0);
+
+    reader.release(90);
+    assert_eq!(waker.count(), 1);
+    assert!(sink.try_grant(100).unwrap());
+}
+
+#[test]
+fn reader_drop_wakes_writer() {
+    let (mut sink, source) = circular_buffer::<u8>(4096);
+    let reader = source.into_view();
+    let capacity = sink.try_grant(usize::MAX).unwrap_err().0;
+    write(&mut sink, capacity);
+
+    let waker = poll_grant(&mut sink, 100);
+    drop(reader);
+    assert_eq!(waker.count(), 1);
+}